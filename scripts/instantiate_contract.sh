@@ -9,7 +9,7 @@ code_id=$(cat $METADATA/code_id.txt)
 oracle_contract_address=$(cat ${SCRIPT_DIR}/../../ica-oracle/scripts/metadata/contract_address.txt)
 
 echo "Instantiating contract..."
-init_msg="{ \"admin_address\": \"$osmo_val\", \"oracle_contract_address\": \"$oracle_contract_address\" }"
+init_msg="{ \"admin_address\": \"$osmo_val\", \"guardian_address\": \"$osmo_val\", \"oracle_contract_address\": \"$oracle_contract_address\", \"max_oracle_staleness_seconds\": 43200, \"min_update_interval_seconds\": 0, \"max_redemption_rate_offset_bps\": 100, \"override_delay_seconds\": 86400, \"oracle_quorum\": 1, \"max_redemption_rate_decrease_bps\": 0 }"
 
 echo ">>> osmosisd tx wasm instantiate $code_id "$init_msg""
 tx_hash=$($OSMOSISD tx wasm instantiate $code_id "$init_msg" --from oval1 --label "st-scaling-factor" --no-admin $GAS -y | grep -E "txhash:" | awk '{print $2}') 
@@ -4,10 +4,11 @@ source ${SCRIPT_DIR}/vars.sh
 
 contract_address=$(cat ${SCRIPT_DIR}/metadata/contract_address.txt)
 
-msg='{ "sudo_adjust_scaling_factors": { "pool_id": 1, "scaling_factors": [100000,120000] }}'
+msg='{ "propose_scaling_factor_override": { "pool_id": 1, "scaling_factors": [100000,120000] }}'
 echo ">>> osmosisd tx wasm execute $contract_address $msg --from oval1"
 $OSMOSISD tx wasm execute $contract_address "$msg" --from oval1 -y $GAS | TRIM_TX
 sleep 6
 
-echo ">>> osmosisd q gamm pools"
-$OSMOSISD q gamm pools
+msg='{ "pending_scaling_factor_overrides": {} }'
+echo ">>> osmosisd q wasm contract-state smart $contract_address $msg"
+$OSMOSISD q wasm contract-state smart $contract_address "$msg"
@@ -1,25 +1,52 @@
+use std::collections::HashMap;
+
 use cosmwasm_std::StdError;
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::{
-    ensure, entry_point, to_binary, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Order,
-    QueryRequest, Response, StdResult, WasmQuery,
+    attr, ensure, entry_point, to_binary, Addr, Attribute, Binary, CosmosMsg, Decimal, Deps,
+    DepsMut, Env, Event, MessageInfo, Order, QueryRequest, Response, StdResult, Storage, WasmQuery,
 };
-use cw2::set_contract_version;
+use cw2::{get_contract_version, set_contract_version};
+use cw_storage_plus::Bound;
 use osmosis_std::types::osmosis::gamm::poolmodels::stableswap::v1beta1::{
     MsgStableSwapAdjustScalingFactors, Pool as StableswapPool,
 };
 use osmosis_std::types::osmosis::poolmanager::v1beta1::PoolmanagerQuerier;
 
 use crate::error::ContractError;
-use crate::helpers::{convert_redemption_rate_to_scaling_factors, validate_pool_configuration};
+use crate::helpers::{
+    apply_redemption_rate_offset, clamp_scaling_factors, convert_pyth_price_to_redemption_rate,
+    convert_redemption_rate_to_scaling_factors, exceeds_max_scaling_factor_change,
+    exceeds_redemption_rate_decrease_tolerance, format_asset_scaling_factors,
+    format_scaling_factors, interpolate_scaling_factors, median_redemption_rate,
+    normalize_scaling_factors_for_decimals, record_redemption_rate_sample,
+    time_weighted_average_redemption_rate, validate_asset_decimals,
+    validate_max_redemption_rate_offset, validate_override_delay, validate_pool_configuration,
+    validate_redemption_rate_offset, validate_scaling_factor_multiplier, within_deadband,
+    DEFAULT_SCALING_FACTOR_MULTIPLIER, MAX_REDEMPTION_RATE_SAMPLES,
+};
+use crate::migrations::{migrate_from_v1_0_0, parse_version};
 use crate::msg::{
-    ExecuteMsg, InstantiateMsg, OracleQueryMsg, Pools, QueryMsg, RedemptionRateResponse,
+    AddPoolMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, OracleQueryMsg, OracleSources,
+    PendingScalingFactorOverrides, PoolSetting, Pools, PythPriceFeedResponse, PythQueryMsg,
+    QueryMsg, RedemptionRateDecreases, RedemptionRateHistory, RedemptionRateResponse,
+    ScalingFactorControllerStatus, ScalingFactorControllers, ScalingFactorRampStatus, SudoMsg,
+    UpdateConfigMsg, UpdatePoolMsg,
+};
+use crate::state::{
+    AssetScalingFactor, CircuitBreakerAction, Config, OracleAdapter, OracleSource, PendingAdmin,
+    PendingScalingFactorOverride, Pool, RedemptionRateDecrease, RedemptionRateSample, RoundingMode,
+    ScalingFactorOverride, ScalingFactorRamp, CONFIG, ORACLE_SOURCES, PAUSED, PENDING_ADMIN,
+    PENDING_SCALING_FACTOR_OVERRIDES, POOLS, REDEMPTION_RATE_DECREASES, REDEMPTION_RATE_HISTORY,
 };
-use crate::state::{AssetOrdering, Config, Pool, CONFIG, POOLS};
 
 const CONTRACT_NAME: &str = "crates.io:stride-st-scaling-factor";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+// Pagination bounds for the number of pools updated in a single batch
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -28,18 +55,140 @@ pub fn instantiate(
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    PAUSED.save(deps.storage, &false)?;
+
+    let attributes = save_config(deps, msg)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attributes(attributes))
+}
+
+/// Validates and stores the full contract config, returning attributes describing the config
+fn save_config(deps: DepsMut, msg: InstantiateMsg) -> Result<Vec<Attribute>, ContractError> {
+    validate_max_redemption_rate_offset(msg.max_redemption_rate_offset_bps)?;
+    validate_override_delay(msg.override_delay_seconds)?;
+    if msg.oracle_quorum == 0 {
+        return Err(ContractError::InvalidOracleQuorum {});
+    }
 
     let config = Config {
         admin_address: deps.api.addr_validate(&msg.admin_address)?,
         oracle_contract_address: deps.api.addr_validate(&msg.oracle_contract_address)?,
+        max_oracle_staleness_seconds: msg.max_oracle_staleness_seconds,
+        guardian_address: deps.api.addr_validate(&msg.guardian_address)?,
+        min_update_interval_seconds: msg.min_update_interval_seconds,
+        max_redemption_rate_offset_bps: msg.max_redemption_rate_offset_bps,
+        override_delay_seconds: msg.override_delay_seconds,
+        oracle_quorum: msg.oracle_quorum,
+        max_redemption_rate_decrease_bps: msg.max_redemption_rate_decrease_bps,
     };
-
     CONFIG.save(deps.storage, &config)?;
 
+    Ok(vec![
+        attr("admin_address", msg.admin_address),
+        attr("guardian_address", msg.guardian_address),
+        attr("oracle_contract_address", msg.oracle_contract_address),
+        attr(
+            "max_oracle_staleness_seconds",
+            msg.max_oracle_staleness_seconds.to_string(),
+        ),
+        attr(
+            "min_update_interval_seconds",
+            msg.min_update_interval_seconds.to_string(),
+        ),
+        attr(
+            "max_redemption_rate_offset_bps",
+            msg.max_redemption_rate_offset_bps.to_string(),
+        ),
+        attr(
+            "override_delay_seconds",
+            msg.override_delay_seconds.to_string(),
+        ),
+        attr("oracle_quorum", msg.oracle_quorum.to_string()),
+        attr(
+            "max_redemption_rate_decrease_bps",
+            msg.max_redemption_rate_decrease_bps.to_string(),
+        ),
+    ])
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    // Confirm the stored contract is this contract and that we're not downgrading
+    let stored_contract = get_contract_version(deps.storage)?;
+    if stored_contract.contract != CONTRACT_NAME {
+        return Err(ContractError::InvalidContractName {
+            expected: CONTRACT_NAME.to_string(),
+            actual: stored_contract.contract,
+        });
+    }
+
+    let stored_version = parse_version(&stored_contract.version)?;
+    if stored_version > parse_version(CONTRACT_VERSION)? {
+        return Err(ContractError::CannotMigrateToOlderVersion {
+            stored_version: stored_contract.version,
+            version: CONTRACT_VERSION.to_string(),
+        });
+    }
+
+    // Run each migration between the stored version and the current version
+    if stored_version < (1, 1, 0) {
+        let guardian_address = msg
+            .guardian_address
+            .map(|address| deps.api.addr_validate(&address))
+            .transpose()?;
+        migrate_from_v1_0_0(
+            deps.storage,
+            guardian_address,
+            msg.max_oracle_staleness_seconds,
+        )?;
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
     Ok(Response::new()
-        .add_attribute("action", "instantiate")
-        .add_attribute("admin_address", msg.admin_address)
-        .add_attribute("oracle_contract_address", msg.oracle_contract_address))
+        .add_attribute("action", "migrate")
+        .add_attribute("previous_version", stored_contract.version)
+        .add_attribute("version", CONTRACT_VERSION))
+}
+
+/// Allows chain governance to intervene without going through the admin
+/// Each message reuses the same logic as the equivalent admin message
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn sudo(deps: DepsMut, env: Env, msg: SudoMsg) -> Result<Response, ContractError> {
+    match msg {
+        SudoMsg::ReplaceConfig(config_msg) => sudo_replace_config(deps, config_msg),
+        SudoMsg::RemovePool { pool_id } => remove_pool(deps, pool_id),
+        SudoMsg::Pause { pool_id } => set_paused(deps, pool_id, true),
+        SudoMsg::Unpause { pool_id } => set_paused(deps, pool_id, false),
+        SudoMsg::AdjustScalingFactors {
+            pool_id,
+            scaling_factors,
+            max_oracle_deviation_bps,
+        } => {
+            let config = CONFIG.load(deps.storage)?;
+            adjust_scaling_factors(
+                deps,
+                env,
+                &config,
+                pool_id,
+                scaling_factors,
+                max_oracle_deviation_bps,
+            )
+        }
+    }
+}
+
+/// Replaces the entire config (including the admin address) through governance
+/// Any pending admin proposal is cancelled, since it was made by the previous admin
+fn sudo_replace_config(mut deps: DepsMut, msg: InstantiateMsg) -> Result<Response, ContractError> {
+    let attributes = save_config(deps.branch(), msg)?;
+    PENDING_ADMIN.remove(deps.storage);
+
+    Ok(Response::new()
+        .add_attribute("action", "replace_config")
+        .add_attributes(attributes))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -50,32 +199,196 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::UpdateConfig {
-            admin_address,
-            oracle_contract_address,
-        } => execute_update_config(deps, info, admin_address, oracle_contract_address),
-        ExecuteMsg::AddPool {
-            pool_id,
-            sttoken_denom,
-            asset_ordering,
-        } => execute_add_pool(deps, info, pool_id, sttoken_denom, asset_ordering),
+        ExecuteMsg::UpdateConfig(update_config_msg) => {
+            execute_update_config(deps, info, update_config_msg)
+        }
+        ExecuteMsg::AddPool(add_pool_msg) => execute_add_pool(deps, env, info, add_pool_msg),
+        ExecuteMsg::UpdatePool(update_pool_msg) => execute_update_pool(deps, info, update_pool_msg),
         ExecuteMsg::RemovePool { pool_id } => execute_remove_pool(deps, info, pool_id),
         ExecuteMsg::UpdateScalingFactor { pool_id } => {
             execute_update_scaling_factor(deps, env, pool_id)
         }
-        ExecuteMsg::SudoAdjustScalingFactors {
+        ExecuteMsg::UpdateAllScalingFactors {
+            pool_ids,
+            start_after,
+            limit,
+        } => execute_update_all_scaling_factors(deps, env, pool_ids, start_after, limit),
+        ExecuteMsg::ProposeScalingFactorOverride {
+            pool_id,
+            scaling_factors,
+            max_oracle_deviation_bps,
+        } => execute_propose_scaling_factor_override(
+            deps,
+            env,
+            info,
             pool_id,
             scaling_factors,
-        } => execute_sudo_adjust_scaling_factors(deps, env, info, pool_id, scaling_factors),
+            max_oracle_deviation_bps,
+        ),
+        ExecuteMsg::CancelScalingFactorOverride { pool_id } => {
+            execute_cancel_scaling_factor_override(deps, info, pool_id)
+        }
+        ExecuteMsg::ExecuteScalingFactorOverride { pool_id } => {
+            execute_execute_scaling_factor_override(deps, env, pool_id)
+        }
+        ExecuteMsg::AcknowledgeRedemptionRateDecrease { pool_id } => {
+            execute_acknowledge_redemption_rate_decrease(deps, info, pool_id)
+        }
+        ExecuteMsg::Pause { pool_id } => execute_pause(deps, info, pool_id),
+        ExecuteMsg::Unpause { pool_id } => execute_unpause(deps, info, pool_id),
+        ExecuteMsg::ProposeAdmin {
+            admin_address,
+            expires_in_seconds,
+        } => execute_propose_admin(deps, env, info, admin_address, expires_in_seconds),
+        ExecuteMsg::AcceptAdmin {} => execute_accept_admin(deps, env, info),
+        ExecuteMsg::CancelAdminProposal {} => execute_cancel_admin_proposal(deps, info),
+        ExecuteMsg::AddOracleSource {
+            contract_address,
+            adapter,
+        } => execute_add_oracle_source(deps, info, contract_address, adapter),
+        ExecuteMsg::RemoveOracleSource { contract_address } => {
+            execute_remove_oracle_source(deps, info, contract_address)
+        }
     }
 }
 
-/// Updates the admin address and oracle contract address from the config
+/// Updates each of the specified fields in the config, leaving the remaining fields unchanged
+/// The previous and new value of each changed field are included in the response attributes
+/// Only the admin can update the config
 pub fn execute_update_config(
     deps: DepsMut,
     info: MessageInfo,
+    msg: UpdateConfigMsg,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    ensure!(
+        info.sender == config.admin_address,
+        ContractError::Unauthorized {}
+    );
+
+    let mut response = Response::new().add_attribute("action", "update_config");
+
+    if let Some(guardian_address) = msg.guardian_address {
+        let guardian_address = deps.api.addr_validate(&guardian_address)?;
+        if guardian_address != config.guardian_address {
+            response = response
+                .add_attribute(
+                    "previous_guardian_address",
+                    config.guardian_address.to_string(),
+                )
+                .add_attribute("guardian_address", guardian_address.to_string());
+            config.guardian_address = guardian_address;
+        }
+    }
+
+    if let Some(oracle_contract_address) = msg.oracle_contract_address {
+        let oracle_contract_address = deps.api.addr_validate(&oracle_contract_address)?;
+        if oracle_contract_address != config.oracle_contract_address {
+            response = response
+                .add_attribute(
+                    "previous_oracle_contract_address",
+                    config.oracle_contract_address.to_string(),
+                )
+                .add_attribute(
+                    "oracle_contract_address",
+                    oracle_contract_address.to_string(),
+                );
+            config.oracle_contract_address = oracle_contract_address;
+        }
+    }
+
+    if let Some(max_staleness) = msg.max_oracle_staleness_seconds {
+        if max_staleness != config.max_oracle_staleness_seconds {
+            response = response
+                .add_attribute(
+                    "previous_max_oracle_staleness_seconds",
+                    config.max_oracle_staleness_seconds.to_string(),
+                )
+                .add_attribute("max_oracle_staleness_seconds", max_staleness.to_string());
+            config.max_oracle_staleness_seconds = max_staleness;
+        }
+    }
+
+    if let Some(min_interval) = msg.min_update_interval_seconds {
+        if min_interval != config.min_update_interval_seconds {
+            response = response
+                .add_attribute(
+                    "previous_min_update_interval_seconds",
+                    config.min_update_interval_seconds.to_string(),
+                )
+                .add_attribute("min_update_interval_seconds", min_interval.to_string());
+            config.min_update_interval_seconds = min_interval;
+        }
+    }
+
+    if let Some(max_offset_bps) = msg.max_redemption_rate_offset_bps {
+        validate_max_redemption_rate_offset(max_offset_bps)?;
+        if max_offset_bps != config.max_redemption_rate_offset_bps {
+            response = response
+                .add_attribute(
+                    "previous_max_redemption_rate_offset_bps",
+                    config.max_redemption_rate_offset_bps.to_string(),
+                )
+                .add_attribute("max_redemption_rate_offset_bps", max_offset_bps.to_string());
+            config.max_redemption_rate_offset_bps = max_offset_bps;
+        }
+    }
+
+    if let Some(override_delay) = msg.override_delay_seconds {
+        validate_override_delay(override_delay)?;
+        if override_delay != config.override_delay_seconds {
+            response = response
+                .add_attribute(
+                    "previous_override_delay_seconds",
+                    config.override_delay_seconds.to_string(),
+                )
+                .add_attribute("override_delay_seconds", override_delay.to_string());
+            config.override_delay_seconds = override_delay;
+        }
+    }
+
+    if let Some(oracle_quorum) = msg.oracle_quorum {
+        if oracle_quorum == 0 {
+            return Err(ContractError::InvalidOracleQuorum {});
+        }
+        if oracle_quorum != config.oracle_quorum {
+            response = response
+                .add_attribute("previous_oracle_quorum", config.oracle_quorum.to_string())
+                .add_attribute("oracle_quorum", oracle_quorum.to_string());
+            config.oracle_quorum = oracle_quorum;
+        }
+    }
+
+    if let Some(max_decrease_bps) = msg.max_redemption_rate_decrease_bps {
+        if max_decrease_bps != config.max_redemption_rate_decrease_bps {
+            response = response
+                .add_attribute(
+                    "previous_max_redemption_rate_decrease_bps",
+                    config.max_redemption_rate_decrease_bps.to_string(),
+                )
+                .add_attribute(
+                    "max_redemption_rate_decrease_bps",
+                    max_decrease_bps.to_string(),
+                );
+            config.max_redemption_rate_decrease_bps = max_decrease_bps;
+        }
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(response)
+}
+
+/// Proposes a new admin address, starting a two-step transfer of the admin role
+/// The transfer is not complete until the proposed admin accepts
+/// Any existing proposal is replaced
+/// Only the admin can propose a new admin
+pub fn execute_propose_admin(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
     admin_address: String,
-    oracle_contract_address: String,
+    expires_in_seconds: Option<u64>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     ensure!(
@@ -83,28 +396,184 @@ pub fn execute_update_config(
         ContractError::Unauthorized {}
     );
 
-    let updated_config = Config {
-        admin_address: deps.api.addr_validate(&admin_address)?,
-        oracle_contract_address: deps.api.addr_validate(&oracle_contract_address)?,
+    let pending_admin = PendingAdmin {
+        address: deps.api.addr_validate(&admin_address)?,
+        expires_at: expires_in_seconds
+            .map(|expiry| env.block.time.seconds().saturating_add(expiry)),
     };
+    PENDING_ADMIN.save(deps.storage, &pending_admin)?;
+
+    let mut response = Response::new()
+        .add_attribute("action", "propose_admin")
+        .add_attribute("pending_admin_address", admin_address);
+    if let Some(expires_at) = pending_admin.expires_at {
+        response = response.add_attribute("expires_at", expires_at.to_string());
+    }
+
+    Ok(response)
+}
+
+/// Accepts the pending admin proposal, transferring the admin role to the sender
+/// Only the proposed admin can accept, and only before the proposal expires
+pub fn execute_accept_admin(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let pending_admin = PENDING_ADMIN
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoPendingAdmin {})?;
+    ensure!(
+        info.sender == pending_admin.address,
+        ContractError::Unauthorized {}
+    );
+    if let Some(expires_at) = pending_admin.expires_at {
+        if env.block.time.seconds() > expires_at {
+            return Err(ContractError::AdminProposalExpired { expires_at });
+        }
+    }
+
+    let mut config = CONFIG.load(deps.storage)?;
+    let previous_admin_address = config.admin_address;
+    config.admin_address = pending_admin.address;
+    CONFIG.save(deps.storage, &config)?;
+    PENDING_ADMIN.remove(deps.storage);
+
+    Ok(Response::new()
+        .add_attribute("action", "accept_admin")
+        .add_attribute("previous_admin_address", previous_admin_address)
+        .add_attribute("admin_address", config.admin_address))
+}
+
+/// Cancels the pending admin proposal
+/// Only the admin can cancel a proposal
+pub fn execute_cancel_admin_proposal(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    ensure!(
+        info.sender == config.admin_address,
+        ContractError::Unauthorized {}
+    );
+
+    if !PENDING_ADMIN.exists(deps.storage) {
+        return Err(ContractError::NoPendingAdmin {});
+    }
+    PENDING_ADMIN.remove(deps.storage);
+
+    Ok(Response::new().add_attribute("action", "cancel_admin_proposal"))
+}
+
+/// Registers an additional oracle that's queried alongside the config's oracle
+/// Only the admin can add an oracle
+pub fn execute_add_oracle_source(
+    deps: DepsMut,
+    info: MessageInfo,
+    contract_address: String,
+    adapter: OracleAdapter,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    ensure!(
+        info.sender == config.admin_address,
+        ContractError::Unauthorized {}
+    );
+
+    let contract_address = deps.api.addr_validate(&contract_address)?;
+    if contract_address == config.oracle_contract_address
+        || ORACLE_SOURCES.has(deps.storage, &contract_address)
+    {
+        return Err(ContractError::OracleSourceAlreadyExists {
+            contract_address: contract_address.to_string(),
+        });
+    }
+
+    let mut response = Response::new()
+        .add_attribute("action", "add_oracle_source")
+        .add_attribute("contract_address", contract_address.to_string())
+        .add_attribute("adapter", adapter.to_string());
+    if let OracleAdapter::Pyth {
+        price_feeds,
+        max_confidence_bps,
+    } = &adapter
+    {
+        let formatted_price_feeds: Vec<String> = price_feeds
+            .iter()
+            .map(|price_feed| format!("{}: {}", price_feed.denom, price_feed.price_feed_id))
+            .collect();
+        response = response
+            .add_attribute(
+                "price_feeds",
+                format!("[{}]", formatted_price_feeds.join(", ")),
+            )
+            .add_attribute("max_confidence_bps", max_confidence_bps.to_string());
+    }
+
+    ORACLE_SOURCES.save(
+        deps.storage,
+        &contract_address,
+        &OracleSource {
+            contract_address: contract_address.clone(),
+            adapter,
+        },
+    )?;
+
+    Ok(response)
+}
+
+/// Removes an additional oracle
+/// Only the admin can remove an oracle
+pub fn execute_remove_oracle_source(
+    deps: DepsMut,
+    info: MessageInfo,
+    contract_address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    ensure!(
+        info.sender == config.admin_address,
+        ContractError::Unauthorized {}
+    );
 
-    CONFIG.save(deps.storage, &updated_config)?;
+    let contract_address = deps.api.addr_validate(&contract_address)?;
+    if !ORACLE_SOURCES.has(deps.storage, &contract_address) {
+        return Err(ContractError::OracleSourceNotFound {
+            contract_address: contract_address.to_string(),
+        });
+    }
+    ORACLE_SOURCES.remove(deps.storage, &contract_address);
 
     Ok(Response::new()
-        .add_attribute("action", "update_config")
-        .add_attribute("admin_address", admin_address)
-        .add_attribute("oracle_contract_address", oracle_contract_address))
+        .add_attribute("action", "remove_oracle_source")
+        .add_attribute("contract_address", contract_address.to_string()))
 }
 
 /// Adds an stToken stableswap pool so that it's scaling factor can be adjusted
 /// Only the admin can add a pool
 pub fn execute_add_pool(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
-    pool_id: u64,
-    sttoken_denom: String,
-    asset_ordering: AssetOrdering,
+    msg: AddPoolMsg,
 ) -> Result<Response, ContractError> {
+    let AddPoolMsg {
+        pool_id,
+        sttoken_denom,
+        asset_ordering,
+        asset_scaling_factors,
+        scaling_factor_multiplier,
+        rounding_mode,
+        asset_decimals,
+        ramp_duration_seconds,
+        deadband_bps,
+        deadband_max_age_seconds,
+        redemption_rate_offset_bps,
+        twap_window_seconds,
+        max_oracle_staleness_seconds,
+        min_update_interval_seconds,
+        circuit_breaker,
+        max_redemption_rate_decrease_bps,
+    } = msg;
+
     let config = CONFIG.load(deps.storage)?;
     ensure!(
         info.sender == config.admin_address,
@@ -116,39 +585,310 @@ pub fn execute_add_pool(
     }
 
     // Query the actual pool from the gamm module
-    let query_pool_resp = PoolmanagerQuerier::new(&deps.querier).pool(pool_id)?;
-    let stableswap_pool: StableswapPool = query_pool_resp
-        .pool
-        .ok_or(ContractError::PoolNotFoundOsmosis { pool_id })?
-        .try_into()
-        .map_err(|e| {
-            StdError::parse_err(
-                "osmosis_std::types::osmosis::gamm::poolmodels::stableswap::v1beta1::Pool",
-                e,
-            )
-        })?;
+    let stableswap_pool = query_stableswap_pool(deps.as_ref(), pool_id)?;
 
-    // Validate that the provided configuration lines up with the actual osmosis pool
-    validate_pool_configuration(
+    // Validate that the provided configuration lines up with the actual osmosis pool,
+    // and determine the scaling factor of each asset if they were not specified
+    let asset_scaling_factors = validate_pool_configuration(
         stableswap_pool,
         pool_id,
         sttoken_denom.clone(),
-        asset_ordering.clone(),
+        asset_ordering,
+        asset_scaling_factors,
+        env.contract.address.as_str(),
     )?;
 
+    // Validate the oracle address of each paired stToken
+    for asset in &asset_scaling_factors {
+        if let AssetScalingFactor::PairedStToken {
+            oracle_contract_address: Some(oracle_contract_address),
+            ..
+        } = asset
+        {
+            deps.api.addr_validate(oracle_contract_address)?;
+        }
+    }
+
+    // Confirm the scaling factors built from the multiplier cannot overflow
+    let scaling_factor_multiplier =
+        scaling_factor_multiplier.unwrap_or(DEFAULT_SCALING_FACTOR_MULTIPLIER);
+    validate_scaling_factor_multiplier(scaling_factor_multiplier)?;
+    if let Some(asset_decimals) = &asset_decimals {
+        validate_asset_decimals(
+            asset_decimals,
+            asset_scaling_factors.len(),
+            scaling_factor_multiplier,
+        )?;
+    }
+    let rounding_mode = rounding_mode.unwrap_or(RoundingMode::Floor);
+    if let Some(offset_bps) = redemption_rate_offset_bps {
+        validate_redemption_rate_offset(offset_bps, config.max_redemption_rate_offset_bps)?;
+    }
+
     let pool = Pool {
         pool_id,
         sttoken_denom: sttoken_denom.clone(),
-        asset_ordering: asset_ordering.clone(),
+        asset_scaling_factors: asset_scaling_factors.clone(),
+        scaling_factor_multiplier,
+        rounding_mode: rounding_mode.clone(),
+        asset_decimals: asset_decimals.clone(),
+        ramp_duration_seconds,
+        scaling_factor_ramp: None,
+        deadband_bps,
+        deadband_max_age_seconds,
+        redemption_rate_offset_bps,
+        twap_window_seconds,
         last_updated: 0,
+        max_oracle_staleness_seconds,
+        min_update_interval_seconds,
+        last_scaling_factors: vec![],
+        circuit_breaker: circuit_breaker.clone(),
+        last_redemption_rate: None,
+        last_oracle_update_time: None,
+        scaling_factor_override: None,
+        max_redemption_rate_decrease_bps,
+        redemption_rate_decrease_acknowledged: false,
+        paused: false,
     };
     POOLS.save(deps.storage, pool_id, &pool)?;
 
-    Ok(Response::new()
+    let mut response = Response::new()
         .add_attribute("action", "add_pool")
         .add_attribute("pool_id", pool_id.to_string())
         .add_attribute("pool_sttoken_denom", sttoken_denom)
-        .add_attribute("pool_asset_ordering", asset_ordering.to_string()))
+        .add_attribute(
+            "pool_asset_scaling_factors",
+            format_asset_scaling_factors(&asset_scaling_factors),
+        )
+        .add_attribute(
+            "scaling_factor_multiplier",
+            scaling_factor_multiplier.to_string(),
+        )
+        .add_attribute("rounding_mode", rounding_mode.to_string());
+    if let Some(asset_decimals) = asset_decimals {
+        response = response.add_attribute("asset_decimals", format!("{:?}", asset_decimals));
+    }
+    if let Some(ramp_duration) = ramp_duration_seconds {
+        response = response.add_attribute("ramp_duration_seconds", ramp_duration.to_string());
+    }
+    if let Some(deadband_bps) = deadband_bps {
+        response = response.add_attribute("deadband_bps", deadband_bps.to_string());
+    }
+    if let Some(max_age) = deadband_max_age_seconds {
+        response = response.add_attribute("deadband_max_age_seconds", max_age.to_string());
+    }
+    if let Some(offset_bps) = redemption_rate_offset_bps {
+        response = response.add_attribute("redemption_rate_offset_bps", offset_bps.to_string());
+    }
+    if let Some(twap_window) = twap_window_seconds {
+        response = response.add_attribute("twap_window_seconds", twap_window.to_string());
+    }
+    if let Some(max_staleness) = max_oracle_staleness_seconds {
+        response =
+            response.add_attribute("max_oracle_staleness_seconds", max_staleness.to_string());
+    }
+    if let Some(min_interval) = min_update_interval_seconds {
+        response = response.add_attribute("min_update_interval_seconds", min_interval.to_string());
+    }
+    if let Some(circuit_breaker) = circuit_breaker {
+        response = response
+            .add_attribute("max_change_bps", circuit_breaker.max_change_bps.to_string())
+            .add_attribute("circuit_breaker_action", circuit_breaker.action.to_string());
+    }
+    if let Some(max_decrease_bps) = max_redemption_rate_decrease_bps {
+        response = response.add_attribute(
+            "max_redemption_rate_decrease_bps",
+            max_decrease_bps.to_string(),
+        );
+    }
+
+    Ok(response)
+}
+
+/// Queries a stableswap pool from Osmosis
+fn query_stableswap_pool(deps: Deps, pool_id: u64) -> Result<StableswapPool, ContractError> {
+    let query_pool_resp = PoolmanagerQuerier::new(&deps.querier).pool(pool_id)?;
+    let stableswap_pool: StableswapPool = query_pool_resp
+        .pool
+        .ok_or(ContractError::PoolNotFoundOsmosis { pool_id })?
+        .try_into()
+        .map_err(|e| {
+            StdError::parse_err(
+                "osmosis_std::types::osmosis::gamm::poolmodels::stableswap::v1beta1::Pool",
+                e,
+            )
+        })?;
+    Ok(stableswap_pool)
+}
+
+/// Confirms the contract is still the scaling factor controller of a pool on Osmosis
+fn ensure_scaling_factor_controller(
+    deps: Deps,
+    env: &Env,
+    pool_id: u64,
+) -> Result<(), ContractError> {
+    let stableswap_pool = query_stableswap_pool(deps, pool_id)?;
+    if stableswap_pool.scaling_factor_controller != env.contract.address.as_str() {
+        return Err(ContractError::NotScalingFactorController {
+            pool_id,
+            scaling_factor_controller: stableswap_pool.scaling_factor_controller,
+        });
+    }
+    Ok(())
+}
+
+/// Updates the configuration of a registered pool
+/// Only the fields that are specified are modified
+/// Only the admin can update a pool
+pub fn execute_update_pool(
+    deps: DepsMut,
+    info: MessageInfo,
+    msg: UpdatePoolMsg,
+) -> Result<Response, ContractError> {
+    let UpdatePoolMsg {
+        pool_id,
+        max_oracle_staleness_seconds,
+        min_update_interval_seconds,
+        circuit_breaker,
+        max_redemption_rate_decrease_bps,
+        rounding_mode,
+        ramp_duration_seconds,
+        deadband_bps,
+        deadband_max_age_seconds,
+        redemption_rate_offset_bps,
+        twap_window_seconds,
+        clear_settings,
+    } = msg;
+
+    let config = CONFIG.load(deps.storage)?;
+    ensure!(
+        info.sender == config.admin_address,
+        ContractError::Unauthorized {}
+    );
+
+    let mut pool = POOLS
+        .may_load(deps.storage, pool_id)?
+        .ok_or(ContractError::PoolNotFound { pool_id })?;
+
+    let mut response = Response::new()
+        .add_attribute("action", "update_pool")
+        .add_attribute("pool_id", pool_id.to_string());
+
+    if let Some(max_staleness) = max_oracle_staleness_seconds {
+        pool.max_oracle_staleness_seconds = Some(max_staleness);
+        response =
+            response.add_attribute("max_oracle_staleness_seconds", max_staleness.to_string());
+    }
+    if let Some(min_interval) = min_update_interval_seconds {
+        pool.min_update_interval_seconds = Some(min_interval);
+        response = response.add_attribute("min_update_interval_seconds", min_interval.to_string());
+    }
+    let circuit_breaker_specified = circuit_breaker.is_some();
+    if let Some(circuit_breaker) = circuit_breaker {
+        response = response
+            .add_attribute("max_change_bps", circuit_breaker.max_change_bps.to_string())
+            .add_attribute("circuit_breaker_action", circuit_breaker.action.to_string());
+        pool.circuit_breaker = Some(circuit_breaker);
+    }
+    if let Some(max_decrease_bps) = max_redemption_rate_decrease_bps {
+        pool.max_redemption_rate_decrease_bps = Some(max_decrease_bps);
+        response = response.add_attribute(
+            "max_redemption_rate_decrease_bps",
+            max_decrease_bps.to_string(),
+        );
+    }
+    if let Some(rounding_mode) = rounding_mode {
+        response = response.add_attribute("rounding_mode", rounding_mode.to_string());
+        pool.rounding_mode = rounding_mode;
+    }
+    if let Some(ramp_duration) = ramp_duration_seconds {
+        // Disabling ramp mode cancels any ramp in progress
+        if ramp_duration == 0 {
+            pool.scaling_factor_ramp = None;
+        }
+        pool.ramp_duration_seconds = Some(ramp_duration);
+        response = response.add_attribute("ramp_duration_seconds", ramp_duration.to_string());
+    }
+    if let Some(deadband_bps) = deadband_bps {
+        pool.deadband_bps = Some(deadband_bps);
+        response = response.add_attribute("deadband_bps", deadband_bps.to_string());
+    }
+    if let Some(max_age) = deadband_max_age_seconds {
+        pool.deadband_max_age_seconds = Some(max_age);
+        response = response.add_attribute("deadband_max_age_seconds", max_age.to_string());
+    }
+    if let Some(offset_bps) = redemption_rate_offset_bps {
+        validate_redemption_rate_offset(offset_bps, config.max_redemption_rate_offset_bps)?;
+        pool.redemption_rate_offset_bps = Some(offset_bps);
+        response = response.add_attribute("redemption_rate_offset_bps", offset_bps.to_string());
+    }
+    if let Some(twap_window) = twap_window_seconds {
+        pool.twap_window_seconds = Some(twap_window);
+        response = response.add_attribute("twap_window_seconds", twap_window.to_string());
+    }
+
+    // Clear any settings that should revert to the default
+    let clear_settings = clear_settings.unwrap_or_default();
+    for setting in &clear_settings {
+        let specified = match setting {
+            PoolSetting::MaxOracleStalenessSeconds => {
+                pool.max_oracle_staleness_seconds = None;
+                max_oracle_staleness_seconds.is_some()
+            }
+            PoolSetting::MinUpdateIntervalSeconds => {
+                pool.min_update_interval_seconds = None;
+                min_update_interval_seconds.is_some()
+            }
+            PoolSetting::CircuitBreaker => {
+                pool.circuit_breaker = None;
+                circuit_breaker_specified
+            }
+            PoolSetting::MaxRedemptionRateDecreaseBps => {
+                pool.max_redemption_rate_decrease_bps = None;
+                max_redemption_rate_decrease_bps.is_some()
+            }
+            PoolSetting::RampDurationSeconds => {
+                pool.ramp_duration_seconds = None;
+                pool.scaling_factor_ramp = None;
+                ramp_duration_seconds.is_some()
+            }
+            PoolSetting::DeadbandBps => {
+                pool.deadband_bps = None;
+                deadband_bps.is_some()
+            }
+            PoolSetting::DeadbandMaxAgeSeconds => {
+                pool.deadband_max_age_seconds = None;
+                deadband_max_age_seconds.is_some()
+            }
+            PoolSetting::RedemptionRateOffsetBps => {
+                pool.redemption_rate_offset_bps = None;
+                redemption_rate_offset_bps.is_some()
+            }
+            PoolSetting::TwapWindowSeconds => {
+                pool.twap_window_seconds = None;
+                twap_window_seconds.is_some()
+            }
+        };
+        if specified {
+            return Err(ContractError::ConflictingPoolSetting {
+                setting: setting.to_string(),
+            });
+        }
+    }
+    if !clear_settings.is_empty() {
+        let cleared_settings: Vec<String> = clear_settings
+            .iter()
+            .map(|setting| setting.to_string())
+            .collect();
+        response = response.add_attribute(
+            "cleared_settings",
+            format!("[{}]", cleared_settings.join(", ")),
+        );
+    }
+
+    POOLS.save(deps.storage, pool_id, &pool)?;
+
+    Ok(response)
 }
 
 /// Removes an stToken stableswap pool, preventing the ability from updating it's scaling factor
@@ -164,10 +904,16 @@ pub fn execute_remove_pool(
         ContractError::Unauthorized {}
     );
 
+    remove_pool(deps, pool_id)
+}
+
+/// Removes a pool, along with any pending override of its scaling factors
+fn remove_pool(deps: DepsMut, pool_id: u64) -> Result<Response, ContractError> {
     if !POOLS.has(deps.storage, pool_id) {
         return Err(ContractError::PoolNotFound { pool_id });
     }
     POOLS.remove(deps.storage, pool_id);
+    PENDING_SCALING_FACTOR_OVERRIDES.remove(deps.storage, pool_id);
 
     Ok(Response::new()
         .add_attribute("action", "remove_pool")
@@ -183,269 +929,1645 @@ pub fn execute_update_scaling_factor(
     pool_id: u64,
 ) -> Result<Response, ContractError> {
     // Confirm the pool has been registered and grab the pool to help specify the query config
-    if !POOLS.has(deps.storage, pool_id) {
-        return Err(ContractError::PoolNotFound { pool_id });
-    }
-    let mut pool = POOLS.load(deps.storage, pool_id)?;
+    let pool = POOLS
+        .may_load(deps.storage, pool_id)?
+        .ok_or(ContractError::PoolNotFound { pool_id })?;
 
-    // Read the oracle contract from the store
-    let oracle_contract_address = &CONFIG.load(deps.storage)?.oracle_contract_address;
+    // Confirm updates have not been paused globally or for this pool
+    if PAUSED.load(deps.storage)? || pool.paused {
+        return Err(ContractError::Paused {});
+    }
 
-    // Build a query to the ICA Oracle contract for the stToken redemption rate
-    let redemption_rate_query_msg = QueryRequest::Wasm(WasmQuery::Smart {
-        contract_addr: oracle_contract_address.to_string(),
-        msg: to_binary(&OracleQueryMsg::RedemptionRate {
-            denom: pool.sttoken_denom.clone(),
-            params: None,
-        })?,
-    });
-
-    // Query the oracle to obtain the stToken redemption rate
-    let redemption_rate_response: RedemptionRateResponse = deps
-        .querier
-        .query(&redemption_rate_query_msg)
-        .map_err(|err| ContractError::UnableToQueryRedemptionRate {
-            token: pool.sttoken_denom.clone(),
-            error: err.to_string(),
-        })?;
-
-    // Build the scaling factors array from the redemption rate
-    let redemption_rate = redemption_rate_response.redemption_rate;
-    let scaling_factors =
-        convert_redemption_rate_to_scaling_factors(redemption_rate, pool.asset_ordering.clone());
-
-    // Submit the `adjust-scaling-factors` transaction to osmosis to update the
-    // factors based on the redemption rate
-    let adjust_factors_msg: CosmosMsg = MsgStableSwapAdjustScalingFactors {
-        sender: env.contract.address.to_string(),
-        pool_id,
-        scaling_factors: scaling_factors.clone(),
-    }
-    .into();
-
-    // Record the block time along side the pool to keep track of when it was last updated
-    pool.last_updated = env.block.time.seconds();
-    POOLS.save(deps.storage, pool_id, &pool)?;
+    // Query the oracle for the stToken redemption rate (and the rate of any paired stTokens)
+    // and apply it to the pool
+    let config = CONFIG.load(deps.storage)?;
+    let oracle_sources = load_oracle_sources(deps.storage)?;
+    let redemption_rates = query_pool_redemption_rates(
+        &config,
+        &pool,
+        &oracle_sources,
+        env.block.time.seconds(),
+        |oracle_source, denom| query_redemption_rate(deps.as_ref(), oracle_source, denom),
+    )?;
+    let update = apply_redemption_rate(deps.storage, &env, &config, pool, redemption_rates)?;
 
     Ok(Response::new()
         .add_attribute("action", "update_scaling_factor")
         .add_attribute("pool_id", pool_id.to_string())
-        .add_attribute("redemption_rate", redemption_rate.to_string())
-        .add_attribute(
-            "scaling_factors",
-            format!("[{}, {}]", scaling_factors[0], scaling_factors[1]),
-        )
-        .add_message(adjust_factors_msg))
+        .add_attributes(update.attributes)
+        .add_events(update.events)
+        .add_messages(update.message))
 }
 
-/// Adjust's the scaling factor of a pool directly by bypassing the query
-/// This is meant as a safety mechanism after the contract is first deployed and
-/// should eventually be removed
-pub fn execute_sudo_adjust_scaling_factors(
+/// Updates the scaling factors of multiple pools in a single transaction
+/// If pool IDs are not specified, all registered pools are updated (paginated by
+/// `start_after` and `limit`)
+/// The oracle is queried once per stToken, and a pool that cannot be updated is skipped
+/// rather than failing the batch, with the reason included in the pool's event
+/// This message is permissionless
+pub fn execute_update_all_scaling_factors(
     deps: DepsMut,
     env: Env,
-    info: MessageInfo,
-    pool_id: u64,
-    scaling_factors: Vec<u64>,
+    pool_ids: Option<Vec<u64>>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
 ) -> Result<Response, ContractError> {
+    // Updates for all pools are blocked while the contract is paused
+    if PAUSED.load(deps.storage)? {
+        return Err(ContractError::Paused {});
+    }
+
     let config = CONFIG.load(deps.storage)?;
-    ensure!(
-        info.sender == config.admin_address,
-        ContractError::Unauthorized {}
-    );
+    let oracle_sources = load_oracle_sources(deps.storage)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
 
-    let adjust_factors_msg: CosmosMsg = MsgStableSwapAdjustScalingFactors {
-        sender: env.contract.address.to_string(),
-        pool_id,
-        scaling_factors: scaling_factors.clone(),
-    }
-    .into();
+    // Determine which pools to update, either from the provided list or from the store
+    let pool_ids: Vec<u64> = match pool_ids {
+        Some(mut pool_ids) => {
+            pool_ids.sort_unstable();
+            pool_ids.dedup();
+            pool_ids
+                .into_iter()
+                .filter(|pool_id| match start_after {
+                    Some(start) => *pool_id > start,
+                    None => true,
+                })
+                .take(limit)
+                .collect()
+        }
+        None => POOLS
+            .keys(
+                deps.storage,
+                start_after.map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit)
+            .collect::<StdResult<Vec<u64>>>()?,
+    };
 
-    Ok(Response::new()
-        .add_attribute("action", "sudo_adjust_scaling_factors")
-        .add_attribute("pool_id", pool_id.to_string())
-        .add_attribute(
-            "scaling_factors",
-            format!("[{},{}]", scaling_factors[0], scaling_factors[1]),
-        )
-        .add_message(adjust_factors_msg))
-}
+    // Cache each redemption rate query so each oracle is only queried once per stToken
+    let mut redemption_rates: HashMap<(String, String), Result<RedemptionRateResponse, String>> =
+        HashMap::new();
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {
-        QueryMsg::Config {} => to_binary(&CONFIG.load(deps.storage)?),
-        QueryMsg::Pool { pool_id } => to_binary(&POOLS.load(deps.storage, pool_id)?),
-        QueryMsg::AllPools {} => to_binary(&query_all_pools(deps)?),
+    let mut response = Response::new().add_attribute("action", "update_all_scaling_factors");
+    let mut updated_pools: Vec<u64> = vec![];
+    let mut skipped_pools: Vec<u64> = vec![];
+
+    for pool_id in pool_ids {
+        let mut event =
+            Event::new("update_scaling_factor").add_attribute("pool_id", pool_id.to_string());
+
+        let update = match POOLS.may_load(deps.storage, pool_id)? {
+            None => Err(ContractError::PoolNotFound { pool_id }.to_string()),
+            Some(pool) if pool.paused => Err(ContractError::Paused {}.to_string()),
+            Some(pool) => {
+                // Confirm the contract still controls the pool's scaling factors, since
+                // Osmosis would otherwise reject the adjust message and fail the whole batch
+                let pool_redemption_rates =
+                    ensure_scaling_factor_controller(deps.as_ref(), &env, pool_id).and_then(|_| {
+                        query_pool_redemption_rates(
+                            &config,
+                            &pool,
+                            &oracle_sources,
+                            env.block.time.seconds(),
+                            |oracle_source, denom| {
+                                redemption_rates
+                                    .entry((
+                                        oracle_source.contract_address.to_string(),
+                                        denom.to_string(),
+                                    ))
+                                    .or_insert_with(|| {
+                                        query_oracle_redemption_rate(
+                                            deps.as_ref(),
+                                            oracle_source,
+                                            denom,
+                                        )
+                                        .map_err(|err| err.to_string())
+                                    })
+                                    .clone()
+                                    .map_err(|error| ContractError::UnableToQueryRedemptionRate {
+                                        token: denom.to_string(),
+                                        error,
+                                    })
+                            },
+                        )
+                    });
+                pool_redemption_rates
+                    .and_then(|redemption_rates| {
+                        apply_redemption_rate(deps.storage, &env, &config, pool, redemption_rates)
+                    })
+                    .map_err(|err| err.to_string())
+            }
+        };
+
+        match update {
+            Ok(update) => {
+                let status = if update.message.is_some() {
+                    updated_pools.push(pool_id);
+                    "updated"
+                } else {
+                    skipped_pools.push(pool_id);
+                    "skipped"
+                };
+                event = event
+                    .add_attribute("status", status)
+                    .add_attributes(update.attributes);
+                response = response
+                    .add_event(event)
+                    .add_events(update.events)
+                    .add_messages(update.message);
+            }
+            Err(reason) => {
+                skipped_pools.push(pool_id);
+                event = event
+                    .add_attribute("status", "skipped")
+                    .add_attribute("reason", reason);
+                response = response.add_event(event);
+            }
+        }
     }
+
+    Ok(response
+        .add_attribute("updated_pools", format!("{:?}", updated_pools))
+        .add_attribute("skipped_pools", format!("{:?}", skipped_pools)))
 }
 
-/// Queries the list of all pools controlled by the contract
-pub fn query_all_pools(deps: Deps) -> StdResult<Pools> {
-    let pools: Vec<Pool> = POOLS
-        .range(deps.storage, None, None, Order::Ascending)
-        .filter_map(|item| item.ok().map(|(_, pool)| pool))
-        .collect();
+/// The outcome of applying a redemption rate to a pool
+struct ScalingFactorUpdate {
+    /// Attributes describing the update, to be added to the response or pool event
+    attributes: Vec<Attribute>,
+    /// Events emitted by the update (e.g. when the circuit breaker trips)
+    events: Vec<Event>,
+    /// The `adjust-scaling-factors` message, or None if the update was not applied
+    message: Option<CosmosMsg>,
+}
 
-    Ok(Pools { pools })
+/// The redemption rates queried for a pool
+struct PoolRedemptionRates {
+    /// The redemption rate of the pool's stToken
+    redemption_rate: RedemptionRateResponse,
+    /// The redemption rate of each paired stToken, in the same order as the pool's assets
+    paired_redemption_rates: Vec<(String, RedemptionRateResponse)>,
+    /// Attributes describing the redemption rate returned by each oracle, if multiple
+    /// oracles were queried
+    oracle_attributes: Vec<Attribute>,
 }
 
-#[cfg(test)]
-mod tests {
-    use std::collections::HashMap;
-    use std::str::FromStr;
-    use std::vec;
+/// Queries the redemption rate of the pool's stToken from the config's oracle (and any
+/// additional oracle sources), as well as the redemption rate of each paired stToken (from
+/// the paired stToken's oracle if specified, otherwise in the same way as the pool's stToken)
+fn query_pool_redemption_rates(
+    config: &Config,
+    pool: &Pool,
+    oracle_sources: &[OracleSource],
+    current_time: u64,
+    mut query: impl FnMut(&OracleSource, &str) -> Result<RedemptionRateResponse, ContractError>,
+) -> Result<PoolRedemptionRates, ContractError> {
+    let max_staleness = pool
+        .max_oracle_staleness_seconds
+        .unwrap_or(config.max_oracle_staleness_seconds);
+    let mut oracle_attributes = vec![];
 
-    use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage};
-    use cosmwasm_std::{
-        attr, from_binary, from_slice, to_binary, Addr, CosmosMsg, Decimal, Empty, Env,
-        MessageInfo, OwnedDeps, Querier, QuerierResult, QueryRequest, SystemError, SystemResult,
-        Timestamp, WasmQuery,
-    };
-    use osmosis_std::types::cosmos::base::v1beta1::Coin;
-    use osmosis_std::types::osmosis::gamm::poolmodels::stableswap::v1beta1::{
-        MsgStableSwapAdjustScalingFactors, Pool as StableswapPool,
-    };
-    use osmosis_std::types::osmosis::poolmanager::v1beta1::PoolRequest;
-    use prost::Message;
-    use serde::{Deserialize, Serialize};
+    let (redemption_rate, attribute) = query_median_redemption_rate(
+        config,
+        &config.oracle_contract_address,
+        oracle_sources,
+        &pool.sttoken_denom,
+        current_time,
+        max_staleness,
+        &mut query,
+    )?;
+    oracle_attributes.extend(attribute);
 
-    use crate::contract::{execute, instantiate, query};
-    use crate::msg::{
-        ExecuteMsg, InstantiateMsg, OracleQueryMsg, Pools, QueryMsg, RedemptionRateResponse,
+    let mut paired_redemption_rates = vec![];
+    for asset in &pool.asset_scaling_factors {
+        if let AssetScalingFactor::PairedStToken {
+            denom,
+            oracle_contract_address,
+        } = asset
+        {
+            // A paired stToken with its own oracle is aggregated in the same way, with the
+            // pool's oracle taking the place of the config's oracle
+            let primary_oracle_address = match oracle_contract_address {
+                Some(oracle_contract_address) => Addr::unchecked(oracle_contract_address),
+                None => config.oracle_contract_address.clone(),
+            };
+            let (paired_redemption_rate, attribute) = query_median_redemption_rate(
+                config,
+                &primary_oracle_address,
+                oracle_sources,
+                denom,
+                current_time,
+                max_staleness,
+                &mut query,
+            )?;
+            oracle_attributes.extend(attribute);
+            paired_redemption_rates.push((denom.clone(), paired_redemption_rate));
+        }
+    }
+
+    Ok(PoolRedemptionRates {
+        redemption_rate,
+        paired_redemption_rates,
+        oracle_attributes,
+    })
+}
+
+/// Queries the redemption rate of an stToken from its primary oracle (the config's oracle,
+/// unless the pool specifies a different oracle for a paired stToken)
+/// If additional oracle sources are registered (or the quorum is more than one), every
+/// oracle is queried and stale or failed responses are discarded. If the remaining
+/// responses meet the quorum, the median redemption rate is returned (with the update time
+/// of the most recent response), along with an attribute describing each oracle's response
+fn query_median_redemption_rate(
+    config: &Config,
+    primary_oracle_address: &Addr,
+    oracle_sources: &[OracleSource],
+    denom: &str,
+    current_time: u64,
+    max_staleness: u64,
+    query: &mut impl FnMut(&OracleSource, &str) -> Result<RedemptionRateResponse, ContractError>,
+) -> Result<(RedemptionRateResponse, Option<Attribute>), ContractError> {
+    let primary_oracle = OracleSource {
+        contract_address: primary_oracle_address.clone(),
+        adapter: OracleAdapter::IcaOracle {},
     };
-    use crate::state::{AssetOrdering, Config, Pool};
-    use crate::ContractError;
+    if oracle_sources.is_empty() && config.oracle_quorum <= 1 {
+        return Ok((query(&primary_oracle, denom)?, None));
+    }
 
-    const ADMIN_ADDRESS: &str = "admin";
-    const ORACLE_ADDRESS: &str = "oracle";
+    let oracle_sources = std::iter::once(&primary_oracle).chain(
+        oracle_sources
+            .iter()
+            .filter(|source| &source.contract_address != primary_oracle_address),
+    );
 
-    const OSMOSIS_POOL_QUERY_TYPE: &str = "/osmosis.poolmanager.v1beta1.Query/Pool";
+    let mut redemption_rates = vec![];
+    let mut update_time = 0;
+    let mut formatted_responses = vec![];
+    for oracle_source in oracle_sources {
+        let formatted_response = match query(oracle_source, denom) {
+            Ok(response) if current_time.saturating_sub(response.update_time) > max_staleness => {
+                "stale".to_string()
+            }
+            Ok(response) => {
+                redemption_rates.push(response.redemption_rate);
+                update_time = update_time.max(response.update_time);
+                response.redemption_rate.to_string()
+            }
+            Err(_) => "unavailable".to_string(),
+        };
+        formatted_responses.push(format!(
+            "{}: {}",
+            oracle_source.contract_address, formatted_response
+        ));
+    }
 
-    // Custom querier used to mock out responses different contracts
-    // The base_querier supports generic bank/wasm/ibc queries
-    // The redemption rates are hard coded into a hashmap that maps
-    // the sttoken denom -> query response
-    // The pools are hard coded into a hashmap that maps pool ID to pool
-    pub struct WasmMockQuerier {
-        base_querier: MockQuerier<Empty>,
-        oracle_redemption_rates: HashMap<String, RedemptionRateResponse>,
-        pools: HashMap<u64, PoolQueryResponse>,
+    let responses = redemption_rates.len() as u64;
+    if responses < config.oracle_quorum {
+        return Err(ContractError::OracleQuorumNotMet {
+            token: denom.to_string(),
+            responses,
+            quorum: config.oracle_quorum,
+        });
     }
+    let redemption_rate = median_redemption_rate(&redemption_rates).ok_or_else(|| {
+        ContractError::InvalidRedemptionRate {
+            token: denom.to_string(),
+        }
+    })?;
 
-    // Custom Osmosis pool query response to get avoid Any proto type
-    #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
-    pub struct PoolQueryResponse {
-        pub pool: StableswapPool,
+    let attribute = attr(
+        "oracle_redemption_rates",
+        format!("{}: [{}]", denom, formatted_responses.join(", ")),
+    );
+    Ok((
+        RedemptionRateResponse {
+            redemption_rate,
+            update_time,
+        },
+        Some(attribute),
+    ))
+}
+
+/// Queries an oracle for the redemption rate of an stToken, using the oracle's adapter
+fn query_redemption_rate(
+    deps: Deps,
+    oracle_source: &OracleSource,
+    sttoken_denom: &str,
+) -> Result<RedemptionRateResponse, ContractError> {
+    query_oracle_redemption_rate(deps, oracle_source, sttoken_denom).map_err(|err| {
+        ContractError::UnableToQueryRedemptionRate {
+            token: sttoken_denom.to_string(),
+            error: err.to_string(),
+        }
+    })
+}
+
+/// Queries the redemption rate of an stToken from either the ICA Oracle, or from the
+/// stToken's price feed in the case of a Pyth oracle
+fn query_oracle_redemption_rate(
+    deps: Deps,
+    oracle_source: &OracleSource,
+    sttoken_denom: &str,
+) -> StdResult<RedemptionRateResponse> {
+    match &oracle_source.adapter {
+        OracleAdapter::IcaOracle {} => {
+            // Build a query to the ICA Oracle contract for the stToken redemption rate
+            let redemption_rate_query_msg = QueryRequest::Wasm(WasmQuery::Smart {
+                contract_addr: oracle_source.contract_address.to_string(),
+                msg: to_binary(&OracleQueryMsg::RedemptionRate {
+                    denom: sttoken_denom.to_string(),
+                    params: None,
+                })?,
+            });
+            deps.querier.query(&redemption_rate_query_msg)
+        }
+        OracleAdapter::Pyth {
+            price_feeds,
+            max_confidence_bps,
+        } => {
+            let price_feed_id = price_feeds
+                .iter()
+                .find(|price_feed| price_feed.denom == sttoken_denom)
+                .ok_or_else(|| {
+                    StdError::generic_err(format!("no price feed for {}", sttoken_denom))
+                })?;
+            let price_feed_query_msg = QueryRequest::Wasm(WasmQuery::Smart {
+                contract_addr: oracle_source.contract_address.to_string(),
+                msg: to_binary(&PythQueryMsg::PriceFeed {
+                    id: price_feed_id.price_feed_id.clone(),
+                })?,
+            });
+            let response: PythPriceFeedResponse = deps.querier.query(&price_feed_query_msg)?;
+            let price = &response.price_feed.price;
+            convert_pyth_price_to_redemption_rate(price, *max_confidence_bps).ok_or_else(|| {
+                StdError::generic_err(format!(
+                    "invalid price {} (confidence {}) from price feed {}",
+                    price.price, price.conf, price_feed_id.price_feed_id
+                ))
+            })
+        }
     }
+}
 
-    // Implements the Querier trait to be used as a MockQuery object
-    impl Querier for WasmMockQuerier {
-        fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
-            let request: QueryRequest<Empty> = match from_slice(bin_request) {
-                Ok(v) => v,
-                Err(e) => {
-                    return SystemResult::Err(SystemError::InvalidRequest {
-                        error: format!("Parsing query request: {}", e),
-                        request: bin_request.into(),
-                    })
-                }
-            };
-            self.handle_query(&request)
+/// Loads each additional oracle source
+fn load_oracle_sources(storage: &dyn Storage) -> StdResult<Vec<OracleSource>> {
+    ORACLE_SOURCES
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, oracle_source)| oracle_source))
+        .collect()
+}
+
+/// Adds the redemption rate from the oracle to the stToken's redemption rate history and
+/// returns the updated history (which is not saved) along with the redemption rate that
+/// should be applied
+/// If a TWAP window is specified, this is the time-weighted average of the redemption rates
+/// observed over the window, otherwise it's the redemption rate from the oracle
+fn sample_redemption_rate(
+    storage: &dyn Storage,
+    denom: &str,
+    response: &RedemptionRateResponse,
+    twap_window_seconds: Option<u64>,
+    current_time: u64,
+) -> StdResult<(Vec<RedemptionRateSample>, Decimal)> {
+    let mut samples = REDEMPTION_RATE_HISTORY
+        .may_load(storage, denom)?
+        .unwrap_or_default();
+    record_redemption_rate_sample(
+        &mut samples,
+        RedemptionRateSample {
+            update_time: response.update_time,
+            redemption_rate: response.redemption_rate,
+        },
+        MAX_REDEMPTION_RATE_SAMPLES,
+    );
+
+    let redemption_rate = match twap_window_seconds {
+        Some(window) if window > 0 => time_weighted_average_redemption_rate(
+            &samples,
+            current_time.saturating_sub(window),
+            current_time,
+        ),
+        _ => None,
+    };
+    let redemption_rate = redemption_rate.unwrap_or(response.redemption_rate);
+    Ok((samples, redemption_rate))
+}
+
+/// Applies the pool's premium or discount to the redemption rate, returning the applied
+/// offset (if the pool has one) and the adjusted redemption rate
+/// The offset is re-bounded by the config's max in case the max was lowered after the
+/// offset was configured
+fn adjust_redemption_rate(
+    config: &Config,
+    pool: &Pool,
+    redemption_rate: Decimal,
+) -> (Option<i64>, Decimal) {
+    let max_offset_bps = config.max_redemption_rate_offset_bps as i64;
+    match pool.redemption_rate_offset_bps {
+        Some(offset_bps) => {
+            let offset_bps = offset_bps.clamp(-max_offset_bps, max_offset_bps);
+            (
+                Some(offset_bps),
+                apply_redemption_rate_offset(redemption_rate, offset_bps),
+            )
         }
+        None => (None, redemption_rate),
     }
+}
 
-    impl WasmMockQuerier {
-        pub fn new() -> Self {
-            WasmMockQuerier {
-                base_querier: MockQuerier::new(&[]),
-                oracle_redemption_rates: HashMap::new(),
-                pools: HashMap::new(),
-            }
+/// Builds the pool's scaling factors array from the redemption rate of the stToken and the
+/// redemption rates of any paired stTokens
+fn build_scaling_factors(
+    pool: &Pool,
+    redemption_rate: Decimal,
+    paired_redemption_rates: &[(String, RedemptionRateResponse)],
+) -> Result<Vec<u64>, ContractError> {
+    let paired_rates: HashMap<String, Decimal> = paired_redemption_rates
+        .iter()
+        .map(|(denom, response)| (denom.clone(), response.redemption_rate))
+        .collect();
+    let scaling_factors = convert_redemption_rate_to_scaling_factors(
+        redemption_rate,
+        &pool.asset_scaling_factors,
+        &paired_rates,
+        pool.scaling_factor_multiplier,
+        &pool.rounding_mode,
+    )?;
+    match &pool.asset_decimals {
+        Some(asset_decimals) => {
+            normalize_scaling_factors_for_decimals(scaling_factors, asset_decimals)
         }
+        None => Ok(scaling_factors),
+    }
+}
 
-        // The only supported queries are oracle redemption rate queries (to the oracle contract address)
-        // stargate pool queries, or generic base queries
-        pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
-            match &request {
-                QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => {
-                    if contract_addr == ORACLE_ADDRESS {
-                        match from_binary(msg).unwrap() {
-                            OracleQueryMsg::RedemptionRate { denom, .. } => {
-                                match self.oracle_redemption_rates.get(&denom) {
-                                    Some(resp) => SystemResult::Ok(to_binary(&resp).into()),
-                                    None => SystemResult::Err(SystemError::Unknown {}),
-                                }
-                            }
-                        }
-                    } else {
-                        panic!("Mocked query not supported for contract {}", contract_addr);
-                    }
-                }
-                QueryRequest::Stargate { path, data } => {
-                    if path == OSMOSIS_POOL_QUERY_TYPE {
-                        let pool_request: PoolRequest = Message::decode(data.as_slice()).unwrap();
-                        match self.pools.get(&pool_request.pool_id) {
-                            Some(resp) => SystemResult::Ok(to_binary(&resp).into()),
-                            None => SystemResult::Err(SystemError::Unknown {}),
-                        }
-                    } else {
-                        panic!("Mocked query not supported for stargate path {}", path);
-                    }
-                }
-                _ => self.base_querier.handle_query(request),
-            }
+/// Rejects the redemption rates if any of them is older than the pool's max oracle staleness
+fn validate_redemption_rate_staleness(
+    env: &Env,
+    config: &Config,
+    pool: &Pool,
+    redemption_rate_response: &RedemptionRateResponse,
+    paired_redemption_rates: &[(String, RedemptionRateResponse)],
+) -> Result<(), ContractError> {
+    let max_staleness = pool
+        .max_oracle_staleness_seconds
+        .unwrap_or(config.max_oracle_staleness_seconds);
+    let update_times = std::iter::once((&pool.sttoken_denom, redemption_rate_response)).chain(
+        paired_redemption_rates
+            .iter()
+            .map(|(denom, response)| (denom, response)),
+    );
+    for (token, response) in update_times {
+        let redemption_rate_age = env
+            .block
+            .time
+            .seconds()
+            .saturating_sub(response.update_time);
+        if redemption_rate_age > max_staleness {
+            return Err(ContractError::StaleRedemptionRate {
+                token: token.to_string(),
+                age: redemption_rate_age,
+                max_staleness,
+            });
         }
+    }
+    Ok(())
+}
 
-        // Adds a mocked entry to the querier such that queries with the specified denom
-        // return a query response with the given redemption rate
-        pub fn mock_oracle_redemption_rate(&mut self, denom: String, redemption_rate: Decimal) {
-            self.oracle_redemption_rates.insert(
-                denom,
-                RedemptionRateResponse {
-                    redemption_rate,
-                    update_time: 1,
-                },
+/// Validates the redemption rates from the oracle against the pool's policies, and if
+/// accepted, records the new scaling factors on the pool and builds the
+/// `adjust-scaling-factors` message
+/// Neither the pool nor the redemption rate history is modified if an error is returned
+fn apply_redemption_rate(
+    storage: &mut dyn Storage,
+    env: &Env,
+    config: &Config,
+    mut pool: Pool,
+    redemption_rates: PoolRedemptionRates,
+) -> Result<ScalingFactorUpdate, ContractError> {
+    let pool_id = pool.pool_id;
+    let PoolRedemptionRates {
+        redemption_rate: redemption_rate_response,
+        paired_redemption_rates,
+        oracle_attributes,
+    } = redemption_rates;
+
+    // Reject the update if the pool was updated too recently
+    let next_update_time = get_next_update_time(config, &pool);
+    if env.block.time.seconds() < next_update_time {
+        return Err(ContractError::UpdateTooFrequent {
+            pool_id,
+            next_update_time,
+        });
+    }
+
+    // Reject the redemption rates if the oracle has not received an update recently
+    validate_redemption_rate_staleness(
+        env,
+        config,
+        &pool,
+        &redemption_rate_response,
+        &paired_redemption_rates,
+    )?;
+
+    // Add each redemption rate to the history, and if the pool has a TWAP window,
+    // apply the time-weighted average of each redemption rate instead of the spot rate
+    // The history is only saved once the redemption rates have been validated
+    let current_time = env.block.time.seconds();
+    let twap_window_seconds = pool.twap_window_seconds.filter(|window| *window > 0);
+    let (samples, redemption_rate) = sample_redemption_rate(
+        storage,
+        &pool.sttoken_denom,
+        &redemption_rate_response,
+        twap_window_seconds,
+        current_time,
+    )?;
+    let mut redemption_rate_histories = vec![(pool.sttoken_denom.clone(), samples)];
+    let mut applied_paired_redemption_rates = vec![];
+    for (denom, response) in &paired_redemption_rates {
+        let (samples, paired_redemption_rate) =
+            sample_redemption_rate(storage, denom, response, twap_window_seconds, current_time)?;
+        redemption_rate_histories.push((denom.clone(), samples));
+        applied_paired_redemption_rates.push((
+            denom.clone(),
+            RedemptionRateResponse {
+                redemption_rate: paired_redemption_rate,
+                update_time: response.update_time,
+            },
+        ));
+    }
+
+    // Confirm the redemption rate has not decreased beyond the pool's tolerance (or the
+    // config's default tolerance)
+    // A larger decrease is only permitted if it was acknowledged by the admin (e.g. after a slash)
+    let mut accepted_decrease: Option<RedemptionRateDecrease> = None;
+    if let Some(previous_redemption_rate) = pool.last_redemption_rate {
+        if redemption_rate < previous_redemption_rate {
+            let tolerance_bps = pool
+                .max_redemption_rate_decrease_bps
+                .unwrap_or(config.max_redemption_rate_decrease_bps);
+            let exceeds_tolerance = exceeds_redemption_rate_decrease_tolerance(
+                previous_redemption_rate,
+                redemption_rate,
+                tolerance_bps,
             );
+            if exceeds_tolerance && !pool.redemption_rate_decrease_acknowledged {
+                return Err(ContractError::RedemptionRateDecreaseExceedsTolerance {
+                    pool_id,
+                    previous_redemption_rate,
+                    redemption_rate,
+                });
+            }
+            accepted_decrease = Some(RedemptionRateDecrease {
+                pool_id,
+                previous_redemption_rate,
+                redemption_rate,
+                time: env.block.time.seconds(),
+                acknowledged: exceeds_tolerance,
+            });
         }
+    }
 
-        // Adds a mocked entry to the querier such that queries with the specified pool ID
-        // return a stableswap pool with specified liquidity
-        pub fn mock_stableswap_pool(&mut self, pool_id: u64, pool: &Pool) {
-            let pool_assets = match pool.asset_ordering {
-                AssetOrdering::StTokenFirst => {
-                    vec![pool.sttoken_denom.clone(), "native_denom".to_string()]
+    // Build the scaling factors array from the redemption rates, after applying the pool's
+    // premium or discount to the redemption rate
+    let (offset_bps, adjusted_redemption_rate) =
+        adjust_redemption_rate(config, &pool, redemption_rate);
+    let mut scaling_factors = build_scaling_factors(
+        &pool,
+        adjusted_redemption_rate,
+        &applied_paired_redemption_rates,
+    )?;
+
+    // If the new scaling factors move too far from the last applied scaling factors,
+    // trip the circuit breaker and either skip the update or clamp the scaling factors
+    let mut update = ScalingFactorUpdate {
+        attributes: vec![attr(
+            "redemption_rate",
+            redemption_rate_response.redemption_rate.to_string(),
+        )],
+        events: vec![],
+        message: None,
+    };
+    update.attributes.extend(oracle_attributes);
+    if twap_window_seconds.is_some() {
+        update
+            .attributes
+            .push(attr("twap_redemption_rate", redemption_rate.to_string()));
+    }
+    if let Some(offset_bps) = offset_bps {
+        update.attributes.extend([
+            attr("redemption_rate_offset_bps", offset_bps.to_string()),
+            attr(
+                "adjusted_redemption_rate",
+                adjusted_redemption_rate.to_string(),
+            ),
+        ]);
+    }
+    if !paired_redemption_rates.is_empty() {
+        let formatted_rates: Vec<String> = paired_redemption_rates
+            .iter()
+            .map(|(denom, response)| format!("{}: {}", denom, response.redemption_rate))
+            .collect();
+        update.attributes.push(attr(
+            "paired_redemption_rates",
+            format!("[{}]", formatted_rates.join(", ")),
+        ));
+        if twap_window_seconds.is_some() {
+            let formatted_rates: Vec<String> = applied_paired_redemption_rates
+                .iter()
+                .map(|(denom, response)| format!("{}: {}", denom, response.redemption_rate))
+                .collect();
+            update.attributes.push(attr(
+                "paired_twap_redemption_rates",
+                format!("[{}]", formatted_rates.join(", ")),
+            ));
+        }
+    }
+
+    if let Some(circuit_breaker) = &pool.circuit_breaker {
+        if exceeds_max_scaling_factor_change(
+            &pool.last_scaling_factors,
+            &scaling_factors,
+            circuit_breaker.max_change_bps,
+        ) {
+            let mut event = Event::new("scaling_factor_circuit_breaker")
+                .add_attribute("pool_id", pool_id.to_string())
+                .add_attribute("circuit_breaker_action", circuit_breaker.action.to_string())
+                .add_attribute("max_change_bps", circuit_breaker.max_change_bps.to_string())
+                .add_attribute(
+                    "previous_scaling_factors",
+                    format_scaling_factors(&pool.last_scaling_factors),
+                )
+                .add_attribute(
+                    "proposed_scaling_factors",
+                    format_scaling_factors(&scaling_factors),
+                );
+
+            match circuit_breaker.action {
+                CircuitBreakerAction::Reject => {
+                    update.attributes.push(attr("circuit_breaker", "rejected"));
+                    update.events.push(event);
+                    return Ok(update);
                 }
-                AssetOrdering::NativeTokenFirst => {
-                    vec!["native_denom".to_string(), pool.sttoken_denom.clone()]
+                CircuitBreakerAction::Clamp => {
+                    scaling_factors = clamp_scaling_factors(
+                        &pool.last_scaling_factors,
+                        &scaling_factors,
+                        circuit_breaker.max_change_bps,
+                    );
+                    event = event.add_attribute(
+                        "applied_scaling_factors",
+                        format_scaling_factors(&scaling_factors),
+                    );
+                    update.attributes.push(attr("circuit_breaker", "clamped"));
+                    update.events.push(event);
                 }
-            };
+            }
+        }
+    }
 
-            let pool_liquidity = pool_assets
-                .into_iter()
-                .map(|denom| Coin {
-                    amount: "1000000".to_string(),
-                    denom,
-                })
-                .collect();
+    // The redemption rates are valid and were not rejected by the circuit breaker,
+    // so they can now be recorded in the history
+    for (denom, samples) in redemption_rate_histories {
+        REDEMPTION_RATE_HISTORY.save(storage, &denom, &samples)?;
+    }
 
-            let stableswap_pool = StableswapPool {
-                id: pool_id,
-                pool_liquidity,
-                ..Default::default()
+    // In ramp mode, the scaling factors move linearly toward the target over the ramp duration,
+    // rather than jumping to the target in a single update
+    // Whenever the target changes, a new ramp is started from the current position of the
+    // previous ramp (or the last applied scaling factors if there was no ramp), and the
+    // previous ramp's end time is kept so that a target that changes on every update
+    // doesn't keep pushing back the ramp
+    let target_scaling_factors = scaling_factors;
+    let ramp_in_progress = pool.scaling_factor_ramp.is_some();
+    let mut ramp_started = false;
+    let scaling_factors = match pool.ramp_duration_seconds {
+        Some(ramp_duration)
+            if ramp_duration > 0
+                && pool.last_scaling_factors.len() == target_scaling_factors.len() =>
+        {
+            let current_time = env.block.time.seconds();
+            let ramp = match pool.scaling_factor_ramp.take() {
+                Some(ramp) if ramp.target_scaling_factors == target_scaling_factors => ramp,
+                previous_ramp => {
+                    ramp_started = true;
+                    let initial_scaling_factors = match &previous_ramp {
+                        Some(previous_ramp) => {
+                            interpolate_scaling_factors(previous_ramp, current_time)
+                        }
+                        None => pool.last_scaling_factors.clone(),
+                    };
+                    let end_time = match previous_ramp {
+                        Some(previous_ramp) if previous_ramp.end_time > current_time => {
+                            previous_ramp.end_time
+                        }
+                        _ => current_time.saturating_add(ramp_duration),
+                    };
+                    ScalingFactorRamp {
+                        initial_scaling_factors,
+                        target_scaling_factors,
+                        start_time: current_time,
+                        end_time,
+                    }
+                }
             };
+            update.attributes.push(attr(
+                "target_scaling_factors",
+                format_scaling_factors(&ramp.target_scaling_factors),
+            ));
+            update
+                .attributes
+                .push(attr("ramp_end_time", ramp.end_time.to_string()));
 
-            self.pools.insert(
-                pool_id,
-                PoolQueryResponse {
-                    pool: stableswap_pool,
-                },
-            );
+            // The ramp is only kept until the target is reached
+            let scaling_factors = interpolate_scaling_factors(&ramp, current_time);
+            if scaling_factors != ramp.target_scaling_factors {
+                pool.scaling_factor_ramp = Some(ramp);
+            }
+            scaling_factors
         }
-
-        // Helper function for if we want to explicitly set a pool that's misconfigured
-        pub fn mock_invalid_stableswap_pool(&mut self, pool_id: u64, pool: StableswapPool) {
-            self.pools.insert(pool_id, PoolQueryResponse { pool });
+        _ => {
+            pool.scaling_factor_ramp = None;
+            target_scaling_factors
+        }
+    };
+
+    // If the oracle has not been updated since the last applied update, skip the update
+    // without submitting a transaction
+    // A ramp in progress or a manual override is still applied from the same redemption rates
+    let oracle_update_time = paired_redemption_rates
+        .iter()
+        .map(|(_, response)| response.update_time)
+        .fold(redemption_rate_response.update_time, u64::max);
+    let oracle_updated = pool
+        .last_oracle_update_time
+        .map_or(true, |last_oracle_update_time| {
+            oracle_update_time > last_oracle_update_time
+        });
+    if !oracle_updated && !ramp_in_progress && pool.scaling_factor_override.is_none() {
+        update.attributes.push(attr("reason", "oracle_not_updated"));
+        return Ok(update);
+    }
+
+    // If the scaling factors are the same as those last applied, skip the update
+    // without submitting a transaction
+    // The pool is only modified to record a newly started ramp
+    if scaling_factors == pool.last_scaling_factors {
+        if ramp_started && pool.scaling_factor_ramp.is_some() {
+            POOLS.save(storage, pool_id, &pool)?;
+            update.attributes.push(attr("ramp", "started"));
+        } else {
+            update
+                .attributes
+                .push(attr("reason", "scaling_factors_unchanged"));
+        }
+        return Ok(update);
+    }
+
+    // If the scaling factors moved by less than the pool's deadband, skip the update to
+    // avoid churning the pool, unless the pool hasn't been updated within the max age
+    // The deadband is also bypassed after a manual override, so that the pool is handed
+    // back to the oracle
+    if let Some(deadband_bps) = pool.deadband_bps {
+        let max_age_exceeded = match pool.deadband_max_age_seconds {
+            Some(max_age) => env.block.time.seconds().saturating_sub(pool.last_updated) >= max_age,
+            None => false,
+        };
+        if !max_age_exceeded
+            && pool.scaling_factor_override.is_none()
+            && within_deadband(&pool.last_scaling_factors, &scaling_factors, deadband_bps)
+        {
+            update.attributes.push(attr("reason", "within_deadband"));
+            return Ok(update);
+        }
+    }
+
+    // Submit the `adjust-scaling-factors` transaction to osmosis to update the
+    // factors based on the redemption rate
+    let adjust_factors_msg: CosmosMsg = MsgStableSwapAdjustScalingFactors {
+        sender: env.contract.address.to_string(),
+        pool_id,
+        scaling_factors: scaling_factors.clone(),
+    }
+    .into();
+
+    // Record the block time and applied scaling factors along side the pool to keep track
+    // of when and how it was last updated
+    pool.last_updated = env.block.time.seconds();
+    pool.last_scaling_factors = scaling_factors.clone();
+    pool.last_redemption_rate = Some(redemption_rate);
+    pool.last_oracle_update_time = Some(oracle_update_time);
+
+    // The oracle has now replaced any manual override of the scaling factors
+    if let Some(scaling_factor_override) = pool.scaling_factor_override.take() {
+        update.attributes.push(attr(
+            "overridden_scaling_factors",
+            format_scaling_factors(&scaling_factor_override.scaling_factors),
+        ));
+    }
+
+    // If the redemption rate decreased, record the decrease for auditing
+    // and consume the admin's acknowledgement if it was required
+    if let Some(decrease) = accepted_decrease {
+        if decrease.acknowledged {
+            pool.redemption_rate_decrease_acknowledged = false;
+        }
+        let sequence = REDEMPTION_RATE_DECREASES
+            .prefix((pool_id, decrease.time))
+            .keys(storage, None, None, Order::Ascending)
+            .count() as u64;
+        REDEMPTION_RATE_DECREASES.save(storage, (pool_id, decrease.time, sequence), &decrease)?;
+        update.events.push(
+            Event::new("redemption_rate_decrease")
+                .add_attribute("pool_id", pool_id.to_string())
+                .add_attribute(
+                    "previous_redemption_rate",
+                    decrease.previous_redemption_rate.to_string(),
+                )
+                .add_attribute("redemption_rate", decrease.redemption_rate.to_string())
+                .add_attribute("acknowledged", decrease.acknowledged.to_string()),
+        );
+    }
+
+    POOLS.save(storage, pool_id, &pool)?;
+
+    update.attributes.push(attr(
+        "scaling_factors",
+        format_scaling_factors(&scaling_factors),
+    ));
+    update.message = Some(adjust_factors_msg);
+
+    Ok(update)
+}
+
+/// Returns the earliest time at which a pool can next be updated, based on when it was
+/// last updated and the minimum update interval (from the pool's override or the config)
+fn get_next_update_time(config: &Config, pool: &Pool) -> u64 {
+    let min_interval = pool
+        .min_update_interval_seconds
+        .unwrap_or(config.min_update_interval_seconds);
+    pool.last_updated.saturating_add(min_interval)
+}
+
+/// Acknowledges that the next redemption rate decrease of a pool may exceed the pool's
+/// tolerance (e.g. after a known slash on Stride)
+/// The acknowledgement permits exactly one decrease, and is consumed by the next update
+/// that applies a decrease beyond the tolerance
+/// Only the admin can acknowledge a decrease
+pub fn execute_acknowledge_redemption_rate_decrease(
+    deps: DepsMut,
+    info: MessageInfo,
+    pool_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    ensure!(
+        info.sender == config.admin_address,
+        ContractError::Unauthorized {}
+    );
+
+    let mut pool = POOLS
+        .may_load(deps.storage, pool_id)?
+        .ok_or(ContractError::PoolNotFound { pool_id })?;
+
+    pool.redemption_rate_decrease_acknowledged = true;
+    POOLS.save(deps.storage, pool_id, &pool)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "acknowledge_redemption_rate_decrease")
+        .add_attribute("pool_id", pool_id.to_string()))
+}
+
+/// Pauses scaling factor updates for a single pool, or for all pools if no pool ID is specified
+/// Either the admin or the guardian can pause
+pub fn execute_pause(
+    deps: DepsMut,
+    info: MessageInfo,
+    pool_id: Option<u64>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    ensure!(
+        info.sender == config.admin_address || info.sender == config.guardian_address,
+        ContractError::Unauthorized {}
+    );
+
+    set_paused(deps, pool_id, true)
+}
+
+/// Unpauses scaling factor updates for a single pool, or for all pools if no pool ID is specified
+/// Only the admin can unpause
+pub fn execute_unpause(
+    deps: DepsMut,
+    info: MessageInfo,
+    pool_id: Option<u64>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    ensure!(
+        info.sender == config.admin_address,
+        ContractError::Unauthorized {}
+    );
+
+    set_paused(deps, pool_id, false)
+}
+
+/// Sets the pause status for either a single pool or the whole contract
+fn set_paused(
+    deps: DepsMut,
+    pool_id: Option<u64>,
+    paused: bool,
+) -> Result<Response, ContractError> {
+    let action = if paused { "pause" } else { "unpause" };
+    let response = Response::new().add_attribute("action", action);
+
+    match pool_id {
+        Some(pool_id) => {
+            let mut pool = POOLS
+                .may_load(deps.storage, pool_id)?
+                .ok_or(ContractError::PoolNotFound { pool_id })?;
+            pool.paused = paused;
+            POOLS.save(deps.storage, pool_id, &pool)?;
+
+            Ok(response.add_attribute("pool_id", pool_id.to_string()))
+        }
+        None => {
+            PAUSED.save(deps.storage, &paused)?;
+
+            Ok(response.add_attribute("pool_id", "all"))
+        }
+    }
+}
+
+/// Immediately overrides the scaling factors of a pool, bypassing the oracle
+fn adjust_scaling_factors(
+    deps: DepsMut,
+    env: Env,
+    config: &Config,
+    pool_id: u64,
+    scaling_factors: Vec<u64>,
+    max_oracle_deviation_bps: Option<u64>,
+) -> Result<Response, ContractError> {
+    let pool = POOLS
+        .may_load(deps.storage, pool_id)?
+        .ok_or(ContractError::PoolNotFound { pool_id })?;
+    let update = apply_scaling_factor_override(
+        deps,
+        &env,
+        config,
+        pool,
+        scaling_factors,
+        max_oracle_deviation_bps,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "sudo_adjust_scaling_factors")
+        .add_attribute("pool_id", pool_id.to_string())
+        .add_attributes(update.attributes)
+        .add_messages(update.message))
+}
+
+/// Proposes a manual override of a pool's scaling factors, which can be executed by anyone
+/// once the config's override delay has passed
+/// Any existing proposal for the pool is replaced
+/// Only the admin can propose an override
+pub fn execute_propose_scaling_factor_override(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pool_id: u64,
+    scaling_factors: Vec<u64>,
+    max_oracle_deviation_bps: Option<u64>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    ensure!(
+        info.sender == config.admin_address,
+        ContractError::Unauthorized {}
+    );
+
+    let pool = POOLS
+        .may_load(deps.storage, pool_id)?
+        .ok_or(ContractError::PoolNotFound { pool_id })?;
+    validate_override_scaling_factors(&pool, &scaling_factors)?;
+
+    let proposed_time = env.block.time.seconds();
+    let executable_time = proposed_time.saturating_add(config.override_delay_seconds);
+    let pending_override = PendingScalingFactorOverride {
+        pool_id,
+        scaling_factors,
+        max_oracle_deviation_bps,
+        proposed_time,
+        executable_time,
+    };
+    PENDING_SCALING_FACTOR_OVERRIDES.save(deps.storage, pool_id, &pending_override)?;
+
+    let mut response = Response::new()
+        .add_attribute("action", "propose_scaling_factor_override")
+        .add_attribute("pool_id", pool_id.to_string())
+        .add_attribute(
+            "scaling_factors",
+            format_scaling_factors(&pending_override.scaling_factors),
+        );
+    if let Some(max_deviation_bps) = max_oracle_deviation_bps {
+        response =
+            response.add_attribute("max_oracle_deviation_bps", max_deviation_bps.to_string());
+    }
+
+    Ok(response.add_attribute("executable_time", executable_time.to_string()))
+}
+
+/// Cancels a pending scaling factor override
+/// Either the admin or guardian can cancel
+pub fn execute_cancel_scaling_factor_override(
+    deps: DepsMut,
+    info: MessageInfo,
+    pool_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    ensure!(
+        info.sender == config.admin_address || info.sender == config.guardian_address,
+        ContractError::Unauthorized {}
+    );
+
+    if !PENDING_SCALING_FACTOR_OVERRIDES.has(deps.storage, pool_id) {
+        return Err(ContractError::NoPendingScalingFactorOverride { pool_id });
+    }
+    PENDING_SCALING_FACTOR_OVERRIDES.remove(deps.storage, pool_id);
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_scaling_factor_override")
+        .add_attribute("pool_id", pool_id.to_string()))
+}
+
+/// Applies a pending scaling factor override once the override delay has passed
+/// The override cannot be executed while the pool is paused
+/// This message is permissionless
+pub fn execute_execute_scaling_factor_override(
+    deps: DepsMut,
+    env: Env,
+    pool_id: u64,
+) -> Result<Response, ContractError> {
+    let pending_override = PENDING_SCALING_FACTOR_OVERRIDES
+        .may_load(deps.storage, pool_id)?
+        .ok_or(ContractError::NoPendingScalingFactorOverride { pool_id })?;
+    if env.block.time.seconds() < pending_override.executable_time {
+        return Err(ContractError::ScalingFactorOverrideNotExecutable {
+            pool_id,
+            executable_time: pending_override.executable_time,
+        });
+    }
+
+    let pool = POOLS
+        .may_load(deps.storage, pool_id)?
+        .ok_or(ContractError::PoolNotFound { pool_id })?;
+    if PAUSED.load(deps.storage)? || pool.paused {
+        return Err(ContractError::Paused {});
+    }
+
+    PENDING_SCALING_FACTOR_OVERRIDES.remove(deps.storage, pool_id);
+
+    let config = CONFIG.load(deps.storage)?;
+    let update = apply_scaling_factor_override(
+        deps,
+        &env,
+        &config,
+        pool,
+        pending_override.scaling_factors,
+        pending_override.max_oracle_deviation_bps,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "execute_scaling_factor_override")
+        .add_attribute("pool_id", pool_id.to_string())
+        .add_attributes(update.attributes)
+        .add_messages(update.message))
+}
+
+/// Validates that a manual override specifies a non-zero scaling factor for each of the
+/// pool's assets
+fn validate_override_scaling_factors(
+    pool: &Pool,
+    scaling_factors: &[u64],
+) -> Result<(), ContractError> {
+    if scaling_factors.len() != pool.asset_scaling_factors.len() {
+        return Err(ContractError::InvalidNumberOfScalingFactors {
+            pool_id: pool.pool_id,
+            number: scaling_factors.len() as u64,
+            expected: pool.asset_scaling_factors.len() as u64,
+        });
+    }
+    if let Some(index) = scaling_factors.iter().position(|factor| *factor == 0) {
+        return Err(ContractError::ZeroScalingFactor {
+            index: index as u64,
+        });
+    }
+    Ok(())
+}
+
+/// Manually overrides the scaling factors of a pool, bypassing the oracle, and builds the
+/// `adjust-scaling-factors` message
+/// If a max deviation is specified, the scaling factors are rejected if they deviate further
+/// from the scaling factors implied by the oracle
+/// The override is recorded on the pool, and any ramp in progress is cancelled
+fn apply_scaling_factor_override(
+    deps: DepsMut,
+    env: &Env,
+    config: &Config,
+    mut pool: Pool,
+    scaling_factors: Vec<u64>,
+    max_oracle_deviation_bps: Option<u64>,
+) -> Result<ScalingFactorUpdate, ContractError> {
+    let pool_id = pool.pool_id;
+    validate_override_scaling_factors(&pool, &scaling_factors)?;
+
+    let mut update = ScalingFactorUpdate {
+        attributes: vec![],
+        events: vec![],
+        message: None,
+    };
+
+    // Optionally confirm the scaling factors are within the max deviation from the
+    // scaling factors implied by the oracle
+    if let Some(max_deviation_bps) = max_oracle_deviation_bps {
+        let oracle_sources = load_oracle_sources(deps.storage)?;
+        let redemption_rates = query_pool_redemption_rates(
+            config,
+            &pool,
+            &oracle_sources,
+            env.block.time.seconds(),
+            |oracle_source, denom| query_redemption_rate(deps.as_ref(), oracle_source, denom),
+        )?;
+
+        // A stale redemption rate can't be used as a reference for the override
+        validate_redemption_rate_staleness(
+            env,
+            config,
+            &pool,
+            &redemption_rates.redemption_rate,
+            &redemption_rates.paired_redemption_rates,
+        )?;
+
+        let (_, adjusted_redemption_rate) = adjust_redemption_rate(
+            config,
+            &pool,
+            redemption_rates.redemption_rate.redemption_rate,
+        );
+        let oracle_scaling_factors = build_scaling_factors(
+            &pool,
+            adjusted_redemption_rate,
+            &redemption_rates.paired_redemption_rates,
+        )?;
+
+        if exceeds_max_scaling_factor_change(
+            &oracle_scaling_factors,
+            &scaling_factors,
+            max_deviation_bps,
+        ) {
+            return Err(ContractError::ScalingFactorsDeviateFromOracle {
+                scaling_factors: format_scaling_factors(&scaling_factors),
+                oracle_scaling_factors: format_scaling_factors(&oracle_scaling_factors),
+                max_deviation_bps,
+            });
+        }
+        update.attributes.extend([
+            attr("max_oracle_deviation_bps", max_deviation_bps.to_string()),
+            attr(
+                "oracle_scaling_factors",
+                format_scaling_factors(&oracle_scaling_factors),
+            ),
+        ]);
+    }
+
+    let adjust_factors_msg: CosmosMsg = MsgStableSwapAdjustScalingFactors {
+        sender: env.contract.address.to_string(),
+        pool_id,
+        scaling_factors: scaling_factors.clone(),
+    }
+    .into();
+
+    // Record the override so that the next update from the oracle is compared against
+    // the overridden scaling factors
+    pool.last_scaling_factors = scaling_factors.clone();
+    pool.scaling_factor_ramp = None;
+    pool.scaling_factor_override = Some(ScalingFactorOverride {
+        scaling_factors: scaling_factors.clone(),
+        time: env.block.time.seconds(),
+    });
+    POOLS.save(deps.storage, pool_id, &pool)?;
+
+    update.attributes.push(attr(
+        "scaling_factors",
+        format_scaling_factors(&scaling_factors),
+    ));
+    update.message = Some(adjust_factors_msg);
+
+    Ok(update)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::Pool { pool_id } => to_binary(&POOLS.load(deps.storage, pool_id)?),
+        QueryMsg::AllPools {} => to_binary(&query_all_pools(deps)?),
+        QueryMsg::PendingAdmin {} => to_binary(&PENDING_ADMIN.may_load(deps.storage)?),
+        QueryMsg::Paused {} => to_binary(&PAUSED.load(deps.storage)?),
+        QueryMsg::NextUpdateTime { pool_id } => to_binary(&query_next_update_time(deps, pool_id)?),
+        QueryMsg::ScalingFactorRamp { pool_id } => {
+            to_binary(&query_scaling_factor_ramp(deps, pool_id)?)
+        }
+        QueryMsg::ScalingFactorControllers {} => {
+            to_binary(&query_scaling_factor_controllers(deps, env)?)
+        }
+        QueryMsg::RedemptionRateDecreases { pool_id } => {
+            to_binary(&query_redemption_rate_decreases(deps, pool_id)?)
+        }
+        QueryMsg::PendingScalingFactorOverrides {} => {
+            to_binary(&query_pending_scaling_factor_overrides(deps)?)
+        }
+        QueryMsg::RedemptionRateHistory {
+            denom,
+            window_seconds,
+        } => to_binary(&query_redemption_rate_history(
+            deps,
+            env,
+            denom,
+            window_seconds,
+        )?),
+        QueryMsg::OracleSources {} => to_binary(&OracleSources {
+            sources: load_oracle_sources(deps.storage)?,
+        }),
+    }
+}
+
+/// Queries the list of all pools controlled by the contract
+pub fn query_all_pools(deps: Deps) -> StdResult<Pools> {
+    let pools: Vec<Pool> = POOLS
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter_map(|item| item.ok().map(|(_, pool)| pool))
+        .collect();
+
+    Ok(Pools { pools })
+}
+
+/// Queries the earliest time at which a pool's scaling factors can next be updated
+pub fn query_next_update_time(deps: Deps, pool_id: u64) -> StdResult<u64> {
+    let config = CONFIG.load(deps.storage)?;
+    let pool = POOLS.load(deps.storage, pool_id)?;
+    Ok(get_next_update_time(&config, &pool))
+}
+
+/// Queries the pool's last applied scaling factors, as well as the target and end time
+/// of the ramp in progress
+pub fn query_scaling_factor_ramp(deps: Deps, pool_id: u64) -> StdResult<ScalingFactorRampStatus> {
+    let pool = POOLS.load(deps.storage, pool_id)?;
+    let (target_scaling_factors, ramp_end_time) = match pool.scaling_factor_ramp {
+        Some(ramp) => (ramp.target_scaling_factors, Some(ramp.end_time)),
+        None => (pool.last_scaling_factors.clone(), None),
+    };
+    Ok(ScalingFactorRampStatus {
+        current_scaling_factors: pool.last_scaling_factors,
+        target_scaling_factors,
+        ramp_end_time,
+    })
+}
+
+/// Queries the scaling factor controller of each registered pool from Osmosis, to confirm
+/// the contract is still able to adjust the pool's scaling factors
+pub fn query_scaling_factor_controllers(
+    deps: Deps,
+    env: Env,
+) -> StdResult<ScalingFactorControllers> {
+    let pool_ids = POOLS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<u64>>>()?;
+
+    let pools = pool_ids
+        .into_iter()
+        .map(|pool_id| {
+            let scaling_factor_controller = query_stableswap_pool(deps, pool_id)
+                .ok()
+                .map(|stableswap_pool| stableswap_pool.scaling_factor_controller);
+            let is_controller =
+                scaling_factor_controller.as_deref() == Some(env.contract.address.as_str());
+            ScalingFactorControllerStatus {
+                pool_id,
+                scaling_factor_controller,
+                is_controller,
+            }
+        })
+        .collect();
+
+    Ok(ScalingFactorControllers { pools })
+}
+
+/// Queries the audit log of accepted redemption rate decreases for a pool
+pub fn query_redemption_rate_decreases(
+    deps: Deps,
+    pool_id: u64,
+) -> StdResult<RedemptionRateDecreases> {
+    let decreases: Vec<RedemptionRateDecrease> = REDEMPTION_RATE_DECREASES
+        .sub_prefix(pool_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter_map(|item| item.ok().map(|(_, decrease)| decrease))
+        .collect();
+
+    Ok(RedemptionRateDecreases { decreases })
+}
+
+/// Queries each scaling factor override that's pending execution
+pub fn query_pending_scaling_factor_overrides(
+    deps: Deps,
+) -> StdResult<PendingScalingFactorOverrides> {
+    let overrides: Vec<PendingScalingFactorOverride> = PENDING_SCALING_FACTOR_OVERRIDES
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter_map(|item| item.ok().map(|(_, pending_override)| pending_override))
+        .collect();
+
+    Ok(PendingScalingFactorOverrides { overrides })
+}
+
+/// Queries the recent redemption rates observed for an stToken, along with their time-weighted
+/// average up to the current block time
+/// If the window is not specified, the average is taken over every observed redemption rate
+pub fn query_redemption_rate_history(
+    deps: Deps,
+    env: Env,
+    denom: String,
+    window_seconds: Option<u64>,
+) -> StdResult<RedemptionRateHistory> {
+    let samples = REDEMPTION_RATE_HISTORY
+        .may_load(deps.storage, &denom)?
+        .unwrap_or_default();
+
+    let current_time = env.block.time.seconds();
+    let start_time = match window_seconds {
+        Some(window) => current_time.saturating_sub(window),
+        None => 0,
+    };
+    let time_weighted_average =
+        time_weighted_average_redemption_rate(&samples, start_time, current_time);
+
+    Ok(RedemptionRateHistory {
+        samples,
+        time_weighted_average,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::str::FromStr;
+    use std::vec;
+
+    use cosmwasm_std::testing::{
+        mock_env, mock_info, MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR,
+    };
+    use cosmwasm_std::{
+        attr, from_binary, from_slice, to_binary, Addr, CosmosMsg, Decimal, Empty, Env, Event,
+        MessageInfo, OwnedDeps, Querier, QuerierResult, QueryRequest, Response, SystemError,
+        SystemResult, Timestamp, WasmQuery,
+    };
+    use osmosis_std::types::cosmos::base::v1beta1::Coin;
+    use osmosis_std::types::osmosis::gamm::poolmodels::stableswap::v1beta1::{
+        MsgStableSwapAdjustScalingFactors, Pool as StableswapPool,
+    };
+    use osmosis_std::types::osmosis::poolmanager::v1beta1::PoolRequest;
+    use prost::Message;
+    use serde::{Deserialize, Serialize};
+
+    use crate::contract::{
+        execute, instantiate, migrate, query, sudo, CONTRACT_NAME, CONTRACT_VERSION,
+    };
+    use crate::helpers::{
+        format_asset_scaling_factors, MAX_REDEMPTION_RATE_OFFSET_LIMIT_BPS,
+        MIN_OVERRIDE_DELAY_SECONDS,
+    };
+    use crate::migrations::{
+        v1_0_0, DEFAULT_MAX_ORACLE_STALENESS_SECONDS, DEFAULT_OVERRIDE_DELAY_SECONDS,
+    };
+    use crate::msg::{
+        AddPoolMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, OracleQueryMsg, OracleSources,
+        PendingScalingFactorOverrides, PoolSetting, Pools, PythPrice, PythPriceFeed,
+        PythPriceFeedResponse, PythQueryMsg, QueryMsg, RedemptionRateDecreases,
+        RedemptionRateHistory, RedemptionRateResponse, ScalingFactorControllerStatus,
+        ScalingFactorControllers, ScalingFactorRampStatus, SudoMsg, UpdateConfigMsg, UpdatePoolMsg,
+    };
+    use crate::state::{
+        AssetOrdering, AssetScalingFactor, CircuitBreaker, CircuitBreakerAction, Config,
+        OracleAdapter, OracleSource, PendingAdmin, PendingScalingFactorOverride, Pool,
+        PythPriceFeedId, RedemptionRateDecrease, RedemptionRateSample, RoundingMode,
+        ScalingFactorOverride,
+    };
+    use crate::ContractError;
+
+    const ADMIN_ADDRESS: &str = "admin";
+    const GUARDIAN_ADDRESS: &str = "guardian";
+    const ORACLE_ADDRESS: &str = "oracle";
+    const PAIRED_ORACLE_ADDRESS: &str = "paired_oracle";
+    const SECONDARY_ORACLE_ADDRESS: &str = "secondary_oracle";
+    const PYTH_ORACLE_ADDRESS: &str = "pyth_oracle";
+    const MAX_ORACLE_STALENESS_SECONDS: u64 = 43_200;
+    const MIN_UPDATE_INTERVAL_SECONDS: u64 = 0;
+    const MAX_REDEMPTION_RATE_OFFSET_BPS: u64 = 100;
+    const OVERRIDE_DELAY_SECONDS: u64 = 3_600;
+    const ORACLE_QUORUM: u64 = 1;
+    // Tests allow any redemption rate decrease unless they configure a tolerance
+    const MAX_REDEMPTION_RATE_DECREASE_BPS: u64 = 10_000;
+
+    const OSMOSIS_POOL_QUERY_TYPE: &str = "/osmosis.poolmanager.v1beta1.Query/Pool";
+
+    // Custom querier used to mock out responses different contracts
+    // The base_querier supports generic bank/wasm/ibc queries
+    // The redemption rates are hard coded into a hashmap that maps
+    // the (oracle address, sttoken denom) -> query response
+    // The pools are hard coded into a hashmap that maps pool ID to pool
+    pub struct WasmMockQuerier {
+        base_querier: MockQuerier<Empty>,
+        oracle_redemption_rates: HashMap<(String, String), RedemptionRateResponse>,
+        pyth_prices: HashMap<String, PythPrice>,
+        pools: HashMap<u64, PoolQueryResponse>,
+    }
+
+    // Custom Osmosis pool query response to get avoid Any proto type
+    #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct PoolQueryResponse {
+        pub pool: StableswapPool,
+    }
+
+    // Implements the Querier trait to be used as a MockQuery object
+    impl Querier for WasmMockQuerier {
+        fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+            let request: QueryRequest<Empty> = match from_slice(bin_request) {
+                Ok(v) => v,
+                Err(e) => {
+                    return SystemResult::Err(SystemError::InvalidRequest {
+                        error: format!("Parsing query request: {}", e),
+                        request: bin_request.into(),
+                    })
+                }
+            };
+            self.handle_query(&request)
+        }
+    }
+
+    impl WasmMockQuerier {
+        pub fn new() -> Self {
+            WasmMockQuerier {
+                base_querier: MockQuerier::new(&[]),
+                oracle_redemption_rates: HashMap::new(),
+                pyth_prices: HashMap::new(),
+                pools: HashMap::new(),
+            }
+        }
+
+        // The only supported queries are oracle redemption rate queries (to the oracle contract addresses)
+        // price feed queries (to the pyth contract address), stargate pool queries, or generic base queries
+        pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
+            match &request {
+                QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => {
+                    if contract_addr == ORACLE_ADDRESS
+                        || contract_addr == PAIRED_ORACLE_ADDRESS
+                        || contract_addr == SECONDARY_ORACLE_ADDRESS
+                    {
+                        match from_binary(msg).unwrap() {
+                            OracleQueryMsg::RedemptionRate { denom, .. } => {
+                                match self
+                                    .oracle_redemption_rates
+                                    .get(&(contract_addr.to_string(), denom))
+                                {
+                                    Some(resp) => SystemResult::Ok(to_binary(&resp).into()),
+                                    None => SystemResult::Err(SystemError::Unknown {}),
+                                }
+                            }
+                        }
+                    } else if contract_addr == PYTH_ORACLE_ADDRESS {
+                        match from_binary(msg).unwrap() {
+                            PythQueryMsg::PriceFeed { id } => match self.pyth_prices.get(&id) {
+                                Some(price) => SystemResult::Ok(
+                                    to_binary(&PythPriceFeedResponse {
+                                        price_feed: PythPriceFeed {
+                                            id,
+                                            price: price.clone(),
+                                            ema_price: price.clone(),
+                                        },
+                                    })
+                                    .into(),
+                                ),
+                                None => SystemResult::Err(SystemError::Unknown {}),
+                            },
+                        }
+                    } else {
+                        panic!("Mocked query not supported for contract {}", contract_addr);
+                    }
+                }
+                QueryRequest::Stargate { path, data } => {
+                    if path == OSMOSIS_POOL_QUERY_TYPE {
+                        let pool_request: PoolRequest = Message::decode(data.as_slice()).unwrap();
+                        match self.pools.get(&pool_request.pool_id) {
+                            Some(resp) => SystemResult::Ok(to_binary(&resp).into()),
+                            None => SystemResult::Err(SystemError::Unknown {}),
+                        }
+                    } else {
+                        panic!("Mocked query not supported for stargate path {}", path);
+                    }
+                }
+                _ => self.base_querier.handle_query(request),
+            }
+        }
+
+        // Adds a mocked entry to the querier such that queries to the default oracle with the
+        // specified denom return a query response with the given redemption rate and update time
+        pub fn mock_oracle_redemption_rate(
+            &mut self,
+            denom: String,
+            redemption_rate: Decimal,
+            update_time: u64,
+        ) {
+            self.mock_redemption_rate_for_oracle(
+                ORACLE_ADDRESS,
+                denom,
+                redemption_rate,
+                update_time,
+            );
+        }
+
+        // Adds a mocked entry to the querier such that queries to the specified oracle with the
+        // specified denom return a query response with the given redemption rate and update time
+        pub fn mock_redemption_rate_for_oracle(
+            &mut self,
+            oracle_contract_address: &str,
+            denom: String,
+            redemption_rate: Decimal,
+            update_time: u64,
+        ) {
+            self.oracle_redemption_rates.insert(
+                (oracle_contract_address.to_string(), denom),
+                RedemptionRateResponse {
+                    redemption_rate,
+                    update_time,
+                },
+            );
+        }
+
+        // Adds a mocked entry to the querier such that queries to the pyth contract with the
+        // specified price feed ID return the given price and confidence interval (with 8 decimals)
+        // and publish time
+        pub fn mock_pyth_price(
+            &mut self,
+            price_feed_id: &str,
+            price: &str,
+            conf: &str,
+            publish_time: i64,
+        ) {
+            self.pyth_prices.insert(
+                price_feed_id.to_string(),
+                PythPrice {
+                    price: price.to_string(),
+                    conf: conf.to_string(),
+                    expo: -8,
+                    publish_time,
+                },
+            );
+        }
+
+        // Adds a mocked entry to the querier such that queries with the specified pool ID
+        // return a stableswap pool with specified liquidity, controlled by the contract
+        pub fn mock_stableswap_pool(&mut self, pool_id: u64, pool: &Pool) {
+            let pool_liquidity = pool
+                .asset_scaling_factors
+                .iter()
+                .enumerate()
+                .map(|(index, asset)| match asset {
+                    AssetScalingFactor::OracleDriven {} => pool.sttoken_denom.clone(),
+                    AssetScalingFactor::NativeAnchor {} => "native_denom".to_string(),
+                    AssetScalingFactor::Fixed { .. } => format!("fixed_denom_{}", index),
+                    AssetScalingFactor::PairedStToken { denom, .. } => denom.clone(),
+                })
+                .map(|denom| Coin {
+                    amount: "1000000".to_string(),
+                    denom,
+                })
+                .collect();
+
+            let stableswap_pool = StableswapPool {
+                id: pool_id,
+                pool_liquidity,
+                scaling_factor_controller: MOCK_CONTRACT_ADDR.to_string(),
+                ..Default::default()
+            };
+
+            self.pools.insert(
+                pool_id,
+                PoolQueryResponse {
+                    pool: stableswap_pool,
+                },
+            );
+        }
+
+        // Helper function for if we want to explicitly set a pool that's misconfigured
+        pub fn mock_invalid_stableswap_pool(&mut self, pool_id: u64, pool: StableswapPool) {
+            self.pools.insert(pool_id, PoolQueryResponse { pool });
         }
     }
 
@@ -458,416 +2580,3956 @@ mod tests {
         let env = mock_env();
         let info = mock_info(ADMIN_ADDRESS, &[]);
 
-        let custom_querier: WasmMockQuerier = WasmMockQuerier::new();
+        let custom_querier: WasmMockQuerier = WasmMockQuerier::new();
+
+        let mut deps = OwnedDeps {
+            storage: MockStorage::default(),
+            api: MockApi::default(),
+            querier: custom_querier,
+            custom_query_type: Default::default(),
+        };
+
+        let msg = InstantiateMsg {
+            admin_address: ADMIN_ADDRESS.to_string(),
+            guardian_address: GUARDIAN_ADDRESS.to_string(),
+            oracle_contract_address: ORACLE_ADDRESS.to_string(),
+            max_oracle_staleness_seconds: MAX_ORACLE_STALENESS_SECONDS,
+            min_update_interval_seconds: MIN_UPDATE_INTERVAL_SECONDS,
+            max_redemption_rate_offset_bps: MAX_REDEMPTION_RATE_OFFSET_BPS,
+            override_delay_seconds: OVERRIDE_DELAY_SECONDS,
+            oracle_quorum: ORACLE_QUORUM,
+            max_redemption_rate_decrease_bps: MAX_REDEMPTION_RATE_DECREASE_BPS,
+        };
+
+        let resp = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        assert_eq!(
+            resp.attributes,
+            vec![
+                attr("action", "instantiate"),
+                attr("admin_address", ADMIN_ADDRESS.to_string()),
+                attr("guardian_address", GUARDIAN_ADDRESS.to_string()),
+                attr("oracle_contract_address", ORACLE_ADDRESS.to_string()),
+                attr(
+                    "max_oracle_staleness_seconds",
+                    MAX_ORACLE_STALENESS_SECONDS.to_string()
+                ),
+                attr(
+                    "min_update_interval_seconds",
+                    MIN_UPDATE_INTERVAL_SECONDS.to_string()
+                ),
+                attr(
+                    "max_redemption_rate_offset_bps",
+                    MAX_REDEMPTION_RATE_OFFSET_BPS.to_string()
+                ),
+                attr("override_delay_seconds", OVERRIDE_DELAY_SECONDS.to_string()),
+                attr("oracle_quorum", ORACLE_QUORUM.to_string()),
+                attr(
+                    "max_redemption_rate_decrease_bps",
+                    MAX_REDEMPTION_RATE_DECREASE_BPS.to_string()
+                ),
+            ]
+        );
+
+        (deps, env, info)
+    }
+
+    // Helper function to create a test Pool object
+    fn get_test_pool(pool_id: u64, sttoken_denom: &str, asset_ordering: AssetOrdering) -> Pool {
+        return Pool {
+            pool_id,
+            sttoken_denom: sttoken_denom.to_string(),
+            asset_scaling_factors: asset_ordering.asset_scaling_factors(),
+            scaling_factor_multiplier: 100_000,
+            rounding_mode: RoundingMode::Floor,
+            asset_decimals: None,
+            ramp_duration_seconds: None,
+            scaling_factor_ramp: None,
+            deadband_bps: None,
+            deadband_max_age_seconds: None,
+            redemption_rate_offset_bps: None,
+            twap_window_seconds: None,
+            last_updated: 0,
+            max_oracle_staleness_seconds: None,
+            min_update_interval_seconds: None,
+            last_scaling_factors: vec![],
+            circuit_breaker: None,
+            last_redemption_rate: None,
+            last_oracle_update_time: None,
+            scaling_factor_override: None,
+            max_redemption_rate_decrease_bps: None,
+            redemption_rate_decrease_acknowledged: false,
+            paused: false,
+        };
+    }
+
+    // Helper function to get an add-pool message from a pool object
+    fn get_add_pool_msg(pool_id: u64, pool: Pool) -> crate::msg::ExecuteMsg {
+        return ExecuteMsg::AddPool(AddPoolMsg {
+            pool_id,
+            sttoken_denom: pool.sttoken_denom,
+            asset_ordering: None,
+            asset_scaling_factors: Some(pool.asset_scaling_factors),
+            scaling_factor_multiplier: Some(pool.scaling_factor_multiplier),
+            rounding_mode: Some(pool.rounding_mode),
+            asset_decimals: pool.asset_decimals,
+            ramp_duration_seconds: pool.ramp_duration_seconds,
+            deadband_bps: pool.deadband_bps,
+            deadband_max_age_seconds: pool.deadband_max_age_seconds,
+            redemption_rate_offset_bps: pool.redemption_rate_offset_bps,
+            twap_window_seconds: pool.twap_window_seconds,
+            max_oracle_staleness_seconds: pool.max_oracle_staleness_seconds,
+            min_update_interval_seconds: pool.min_update_interval_seconds,
+            circuit_breaker: pool.circuit_breaker,
+            max_redemption_rate_decrease_bps: pool.max_redemption_rate_decrease_bps,
+        });
+    }
+
+    // Helper function to mock out the oracle redemption rate and then update
+    // the scaling factor of a pool at the given block time
+    fn update_scaling_factor_at(
+        deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier, Empty>,
+        env: &mut Env,
+        pool_id: u64,
+        sttoken_denom: &str,
+        redemption_rate: &str,
+        block_time: u64,
+    ) -> Result<Response, ContractError> {
+        env.block.time = Timestamp::from_seconds(block_time);
+        deps.querier.mock_oracle_redemption_rate(
+            sttoken_denom.to_string(),
+            Decimal::from_str(redemption_rate).unwrap(),
+            block_time,
+        );
+        let update_msg = ExecuteMsg::UpdateScalingFactor { pool_id };
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("keeper", &[]),
+            update_msg,
+        )
+    }
+
+    #[test]
+    fn test_instantiate() {
+        let (deps, env, _) = default_instantiate();
+
+        // Confirm addresses were set properly
+        let msg = QueryMsg::Config {};
+        let resp = query(deps.as_ref(), env, msg).unwrap();
+        let config: Config = from_binary(&resp).unwrap();
+        assert_eq!(
+            config,
+            Config {
+                admin_address: Addr::unchecked(ADMIN_ADDRESS.to_string()),
+                oracle_contract_address: Addr::unchecked(ORACLE_ADDRESS.to_string()),
+                max_oracle_staleness_seconds: MAX_ORACLE_STALENESS_SECONDS,
+                guardian_address: Addr::unchecked(GUARDIAN_ADDRESS.to_string()),
+                min_update_interval_seconds: MIN_UPDATE_INTERVAL_SECONDS,
+                max_redemption_rate_offset_bps: MAX_REDEMPTION_RATE_OFFSET_BPS,
+                override_delay_seconds: OVERRIDE_DELAY_SECONDS,
+                oracle_quorum: ORACLE_QUORUM,
+                max_redemption_rate_decrease_bps: MAX_REDEMPTION_RATE_DECREASE_BPS,
+            }
+        )
+    }
+
+    #[test]
+    fn test_update_config() {
+        let (mut deps, env, info) = default_instantiate();
+
+        // Update only the oracle address
+        let updated_oracle = "updated_oracle";
+        let update_msg = ExecuteMsg::UpdateConfig(UpdateConfigMsg {
+            oracle_contract_address: Some(updated_oracle.to_string()),
+            ..Default::default()
+        });
+        let resp = execute(deps.as_mut(), env.clone(), info.clone(), update_msg).unwrap();
+        assert_eq!(
+            resp.attributes,
+            vec![
+                attr("action", "update_config"),
+                attr("previous_oracle_contract_address", ORACLE_ADDRESS),
+                attr("oracle_contract_address", updated_oracle),
+            ]
+        );
+
+        // Confirm only the oracle address was updated
+        let query_config_msg = QueryMsg::Config {};
+        let query_resp = query(deps.as_ref(), env.clone(), query_config_msg).unwrap();
+        let updated_config: Config = from_binary(&query_resp).unwrap();
+        assert_eq!(
+            updated_config,
+            Config {
+                admin_address: Addr::unchecked(ADMIN_ADDRESS.to_string()),
+                oracle_contract_address: Addr::unchecked(updated_oracle.to_string()),
+                max_oracle_staleness_seconds: MAX_ORACLE_STALENESS_SECONDS,
+                guardian_address: Addr::unchecked(GUARDIAN_ADDRESS.to_string()),
+                min_update_interval_seconds: MIN_UPDATE_INTERVAL_SECONDS,
+                max_redemption_rate_offset_bps: MAX_REDEMPTION_RATE_OFFSET_BPS,
+                override_delay_seconds: OVERRIDE_DELAY_SECONDS,
+                oracle_quorum: ORACLE_QUORUM,
+                max_redemption_rate_decrease_bps: MAX_REDEMPTION_RATE_DECREASE_BPS,
+            }
+        );
+
+        // Update the guardian, the default staleness, the default update interval, and the max
+        // redemption rate offset, while resubmitting the same oracle
+        // The oracle address should be omitted from the attributes since it didn't change
+        let updated_guardian = "updated_guardian";
+        let updated_staleness = 3600;
+        let updated_interval = 60;
+        let updated_max_offset = 200;
+        let update_msg = ExecuteMsg::UpdateConfig(UpdateConfigMsg {
+            guardian_address: Some(updated_guardian.to_string()),
+            oracle_contract_address: Some(updated_oracle.to_string()),
+            max_oracle_staleness_seconds: Some(updated_staleness),
+            min_update_interval_seconds: Some(updated_interval),
+            max_redemption_rate_offset_bps: Some(updated_max_offset),
+            override_delay_seconds: None,
+            oracle_quorum: None,
+            max_redemption_rate_decrease_bps: None,
+        });
+        let resp = execute(deps.as_mut(), env.clone(), info.clone(), update_msg).unwrap();
+        assert_eq!(
+            resp.attributes,
+            vec![
+                attr("action", "update_config"),
+                attr("previous_guardian_address", GUARDIAN_ADDRESS),
+                attr("guardian_address", updated_guardian),
+                attr(
+                    "previous_max_oracle_staleness_seconds",
+                    MAX_ORACLE_STALENESS_SECONDS.to_string()
+                ),
+                attr("max_oracle_staleness_seconds", "3600"),
+                attr(
+                    "previous_min_update_interval_seconds",
+                    MIN_UPDATE_INTERVAL_SECONDS.to_string()
+                ),
+                attr("min_update_interval_seconds", "60"),
+                attr(
+                    "previous_max_redemption_rate_offset_bps",
+                    MAX_REDEMPTION_RATE_OFFSET_BPS.to_string()
+                ),
+                attr("max_redemption_rate_offset_bps", "200"),
+            ]
+        );
+
+        let query_config_msg = QueryMsg::Config {};
+        let query_resp = query(deps.as_ref(), env.clone(), query_config_msg).unwrap();
+        let updated_config: Config = from_binary(&query_resp).unwrap();
+        assert_eq!(
+            updated_config,
+            Config {
+                admin_address: Addr::unchecked(ADMIN_ADDRESS.to_string()),
+                oracle_contract_address: Addr::unchecked(updated_oracle.to_string()),
+                max_oracle_staleness_seconds: updated_staleness,
+                guardian_address: Addr::unchecked(updated_guardian.to_string()),
+                min_update_interval_seconds: updated_interval,
+                max_redemption_rate_offset_bps: updated_max_offset,
+                override_delay_seconds: OVERRIDE_DELAY_SECONDS,
+                oracle_quorum: ORACLE_QUORUM,
+                max_redemption_rate_decrease_bps: MAX_REDEMPTION_RATE_DECREASE_BPS,
+            }
+        );
+
+        // The max redemption rate offset must be within the protocol limit
+        let update_msg = ExecuteMsg::UpdateConfig(UpdateConfigMsg {
+            max_redemption_rate_offset_bps: Some(MAX_REDEMPTION_RATE_OFFSET_LIMIT_BPS + 1),
+            ..Default::default()
+        });
+        let resp = execute(deps.as_mut(), env.clone(), info.clone(), update_msg);
+        assert_eq!(
+            resp,
+            Err(ContractError::InvalidMaxRedemptionRateOffset {
+                max_offset_bps: MAX_REDEMPTION_RATE_OFFSET_LIMIT_BPS + 1,
+                max_offset_limit_bps: MAX_REDEMPTION_RATE_OFFSET_LIMIT_BPS,
+            })
+        );
+
+        // The override delay can't be removed
+        let update_msg = ExecuteMsg::UpdateConfig(UpdateConfigMsg {
+            override_delay_seconds: Some(0),
+            ..Default::default()
+        });
+        let resp = execute(deps.as_mut(), env.clone(), info, update_msg);
+        assert_eq!(
+            resp,
+            Err(ContractError::InvalidOverrideDelay {
+                override_delay_seconds: 0,
+                min_override_delay_seconds: MIN_OVERRIDE_DELAY_SECONDS,
+            })
+        );
+
+        // Only the admin can update the config
+        let update_msg = ExecuteMsg::UpdateConfig(UpdateConfigMsg::default());
+        let resp = execute(deps.as_mut(), env, mock_info("not_admin", &[]), update_msg);
+        assert_eq!(resp, Err(ContractError::Unauthorized {}))
+    }
+
+    #[test]
+    fn test_admin_transfer() {
+        let (mut deps, mut env, admin_info) = default_instantiate();
+        let new_admin = "new_admin";
+        let new_admin_info = mock_info(new_admin, &[]);
+
+        // Attempting to accept or cancel before a proposal exists should fail
+        let accept_msg = ExecuteMsg::AcceptAdmin {};
+        let resp = execute(
+            deps.as_mut(),
+            env.clone(),
+            new_admin_info.clone(),
+            accept_msg.clone(),
+        );
+        assert_eq!(resp, Err(ContractError::NoPendingAdmin {}));
+
+        let cancel_msg = ExecuteMsg::CancelAdminProposal {};
+        let resp = execute(
+            deps.as_mut(),
+            env.clone(),
+            admin_info.clone(),
+            cancel_msg.clone(),
+        );
+        assert_eq!(resp, Err(ContractError::NoPendingAdmin {}));
+
+        // Only the admin can propose a new admin
+        let propose_msg = ExecuteMsg::ProposeAdmin {
+            admin_address: new_admin.to_string(),
+            expires_in_seconds: Some(100),
+        };
+        let resp = execute(
+            deps.as_mut(),
+            env.clone(),
+            new_admin_info.clone(),
+            propose_msg.clone(),
+        );
+        assert_eq!(resp, Err(ContractError::Unauthorized {}));
+
+        // Propose the new admin with an expiry
+        let block_time = 1_000;
+        env.block.time = Timestamp::from_seconds(block_time);
+        let resp = execute(
+            deps.as_mut(),
+            env.clone(),
+            admin_info.clone(),
+            propose_msg.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            resp.attributes,
+            vec![
+                attr("action", "propose_admin"),
+                attr("pending_admin_address", new_admin),
+                attr("expires_at", "1100"),
+            ]
+        );
+
+        let query_resp = query(deps.as_ref(), env.clone(), QueryMsg::PendingAdmin {}).unwrap();
+        let pending_admin: Option<PendingAdmin> = from_binary(&query_resp).unwrap();
+        assert_eq!(
+            pending_admin,
+            Some(PendingAdmin {
+                address: Addr::unchecked(new_admin),
+                expires_at: Some(1100),
+            })
+        );
+
+        // Cancel the proposal, and confirm it was removed
+        let resp = execute(deps.as_mut(), env.clone(), admin_info.clone(), cancel_msg).unwrap();
+        assert_eq!(
+            resp.attributes,
+            vec![attr("action", "cancel_admin_proposal")]
+        );
+
+        let query_resp = query(deps.as_ref(), env.clone(), QueryMsg::PendingAdmin {}).unwrap();
+        let pending_admin: Option<PendingAdmin> = from_binary(&query_resp).unwrap();
+        assert_eq!(pending_admin, None);
+
+        // Propose again, and attempt to accept after the expiry, it should fail
+        execute(deps.as_mut(), env.clone(), admin_info.clone(), propose_msg).unwrap();
+        env.block.time = Timestamp::from_seconds(block_time + 101);
+        let resp = execute(
+            deps.as_mut(),
+            env.clone(),
+            new_admin_info.clone(),
+            accept_msg.clone(),
+        );
+        assert_eq!(
+            resp,
+            Err(ContractError::AdminProposalExpired { expires_at: 1100 })
+        );
+
+        // Propose without an expiry, and attempt to accept from a different address
+        let propose_msg = ExecuteMsg::ProposeAdmin {
+            admin_address: new_admin.to_string(),
+            expires_in_seconds: None,
+        };
+        execute(deps.as_mut(), env.clone(), admin_info.clone(), propose_msg).unwrap();
+        let resp = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("other", &[]),
+            accept_msg.clone(),
+        );
+        assert_eq!(resp, Err(ContractError::Unauthorized {}));
+
+        // Accept as the new admin
+        let resp = execute(
+            deps.as_mut(),
+            env.clone(),
+            new_admin_info.clone(),
+            accept_msg,
+        )
+        .unwrap();
+        assert_eq!(
+            resp.attributes,
+            vec![
+                attr("action", "accept_admin"),
+                attr("previous_admin_address", ADMIN_ADDRESS),
+                attr("admin_address", new_admin),
+            ]
+        );
+
+        // Confirm the config was updated and the proposal was cleared
+        let query_resp = query(deps.as_ref(), env.clone(), QueryMsg::Config {}).unwrap();
+        let config: Config = from_binary(&query_resp).unwrap();
+        assert_eq!(config.admin_address, Addr::unchecked(new_admin));
+
+        let query_resp = query(deps.as_ref(), env.clone(), QueryMsg::PendingAdmin {}).unwrap();
+        let pending_admin: Option<PendingAdmin> = from_binary(&query_resp).unwrap();
+        assert_eq!(pending_admin, None);
+
+        // The old admin should no longer be able to administer the contract
+        let pause_msg = ExecuteMsg::Unpause { pool_id: None };
+        let resp = execute(deps.as_mut(), env.clone(), admin_info, pause_msg.clone());
+        assert_eq!(resp, Err(ContractError::Unauthorized {}));
+        execute(deps.as_mut(), env, new_admin_info, pause_msg).unwrap();
+    }
+
+    #[test]
+    fn test_add_remove_pools() {
+        let (mut deps, env, info) = default_instantiate();
+
+        // Create 3 dummy pools
+        let pool1 = get_test_pool(1, "stA", AssetOrdering::StTokenFirst);
+        let pool2 = get_test_pool(2, "stB", AssetOrdering::NativeTokenFirst);
+        let pool3 = get_test_pool(3, "stC", AssetOrdering::StTokenFirst);
+
+        // Mock each pool in the querier
+        deps.querier.mock_stableswap_pool(1, &pool1);
+        deps.querier.mock_stableswap_pool(2, &pool2);
+        deps.querier.mock_stableswap_pool(3, &pool3);
+
+        // Add each pool, and confirm the attributes and pool-query for each
+        for pool in vec![pool1.clone(), pool2.clone(), pool3.clone()] {
+            let add_msg = get_add_pool_msg(pool.pool_id, pool.clone());
+            let add_msg_resp = execute(deps.as_mut(), env.clone(), info.clone(), add_msg).unwrap();
+
+            assert_eq!(
+                add_msg_resp.attributes,
+                vec![
+                    attr("action", "add_pool"),
+                    attr("pool_id", pool.pool_id.to_string()),
+                    attr("pool_sttoken_denom", pool.sttoken_denom.clone()),
+                    attr(
+                        "pool_asset_scaling_factors",
+                        format_asset_scaling_factors(&pool.asset_scaling_factors)
+                    ),
+                    attr(
+                        "scaling_factor_multiplier",
+                        pool.scaling_factor_multiplier.to_string()
+                    ),
+                    attr("rounding_mode", pool.rounding_mode.to_string()),
+                ]
+            );
+
+            let query_pool_msg = QueryMsg::Pool {
+                pool_id: pool.pool_id,
+            };
+            let query_resp = query(deps.as_ref(), env.clone(), query_pool_msg).unwrap();
+            let pool_resp: Pool = from_binary(&query_resp).unwrap();
+
+            assert_eq!(pool_resp, pool);
+        }
+
+        // Test the AllPools query
+        let all_pools_query_msg = QueryMsg::AllPools {};
+        let query_pools_resp = query(deps.as_ref(), env.clone(), all_pools_query_msg).unwrap();
+        let all_pools_resp: Pools = from_binary(&query_pools_resp).unwrap();
+
+        assert_eq!(
+            all_pools_resp,
+            Pools {
+                pools: vec![pool1.clone(), pool2, pool3.clone()]
+            }
+        );
+
+        // Remove pool 2
+        let remove_pool_msg = ExecuteMsg::RemovePool { pool_id: 2 };
+        let remove_pool_resp =
+            execute(deps.as_mut(), env.clone(), info.clone(), remove_pool_msg).unwrap();
+
+        assert_eq!(
+            remove_pool_resp.attributes,
+            vec![attr("action", "remove_pool"), attr("pool_id", "2")]
+        );
+
+        // Query AllPools again, it should only return pools 1 and 3
+        let all_pools_query_msg = QueryMsg::AllPools {};
+        let query_pools_resp = query(deps.as_ref(), env.clone(), all_pools_query_msg).unwrap();
+        let all_pools_resp: Pools = from_binary(&query_pools_resp).unwrap();
+
+        assert_eq!(
+            all_pools_resp,
+            Pools {
+                pools: vec![pool1, pool3]
+            }
+        );
+
+        // Try to add pool 1 again, it should fail
+        let add_duplicate_pool_msg = ExecuteMsg::AddPool(AddPoolMsg {
+            pool_id: 1,
+            sttoken_denom: "".to_string(),
+            asset_ordering: Some(AssetOrdering::StTokenFirst),
+            asset_scaling_factors: None,
+            scaling_factor_multiplier: None,
+            rounding_mode: None,
+            asset_decimals: None,
+            ramp_duration_seconds: None,
+            deadband_bps: None,
+            deadband_max_age_seconds: None,
+            redemption_rate_offset_bps: None,
+            twap_window_seconds: None,
+            max_oracle_staleness_seconds: None,
+            min_update_interval_seconds: None,
+            circuit_breaker: None,
+            max_redemption_rate_decrease_bps: None,
+        });
+        let add_duplicate_pool_resp = execute(deps.as_mut(), env, info, add_duplicate_pool_msg);
+        assert_eq!(
+            add_duplicate_pool_resp,
+            Err(ContractError::PoolAlreadyExists { pool_id: 1 })
+        )
+    }
+
+    #[test]
+    fn test_add_misconfigured_pool_id_mismatch() {
+        let (mut deps, env, info) = default_instantiate();
+
+        let queried_id = 1;
+        let misconfigured_pool_id = 999;
+
+        // Create a pool configuration and message
+        let pool = get_test_pool(queried_id, "sttoken", AssetOrdering::StTokenFirst);
+        let add_msg = get_add_pool_msg(queried_id, pool.clone());
+
+        // Mock out the query response so that the returned pool has a different pool ID
+        deps.querier.mock_invalid_stableswap_pool(
+            queried_id,
+            StableswapPool {
+                id: misconfigured_pool_id,
+                ..Default::default()
+            },
+        );
+
+        // Attempt to add the pool, it should error since the ID does not match
+        let resp = execute(deps.as_mut(), env.clone(), info.clone(), add_msg);
+        assert_eq!(
+            resp,
+            Err(ContractError::PoolNotFoundOsmosis {
+                pool_id: queried_id
+            })
+        );
+    }
+
+    #[test]
+    fn test_add_misconfigured_pool_number_of_assets() {
+        let (mut deps, env, info) = default_instantiate();
+
+        // Create an add message that uses the two-asset ordering
+        let pool_id = 1;
+        let add_msg = ExecuteMsg::AddPool(AddPoolMsg {
+            pool_id,
+            sttoken_denom: "sttoken".to_string(),
+            asset_ordering: Some(AssetOrdering::StTokenFirst),
+            asset_scaling_factors: None,
+            scaling_factor_multiplier: None,
+            rounding_mode: None,
+            asset_decimals: None,
+            ramp_duration_seconds: None,
+            deadband_bps: None,
+            deadband_max_age_seconds: None,
+            redemption_rate_offset_bps: None,
+            twap_window_seconds: None,
+            max_oracle_staleness_seconds: None,
+            min_update_interval_seconds: None,
+            circuit_breaker: None,
+            max_redemption_rate_decrease_bps: None,
+        });
+
+        // Mock out the query response so that the returned pool has a more than 2 assets
+        deps.querier.mock_invalid_stableswap_pool(
+            pool_id,
+            StableswapPool {
+                id: pool_id,
+                pool_liquidity: vec![
+                    Coin {
+                        denom: "sttoken".to_string(),
+                        amount: "1000000".to_string(),
+                    },
+                    Coin {
+                        denom: "denom2".to_string(),
+                        amount: "1000000".to_string(),
+                    },
+                    Coin {
+                        denom: "denom3".to_string(),
+                        amount: "1000000".to_string(),
+                    },
+                ],
+                scaling_factor_controller: MOCK_CONTRACT_ADDR.to_string(),
+                ..Default::default()
+            },
+        );
+
+        // Attempt to add the pool, it should error since the ordering only describes two assets
+        let resp = execute(deps.as_mut(), env.clone(), info.clone(), add_msg);
+        assert_eq!(
+            resp,
+            Err(ContractError::InvalidNumberOfPoolAssets { number: 3 })
+        );
+    }
+
+    #[test]
+    fn test_add_misconfigured_pool_asset_ordering() {
+        let (mut deps, env, info) = default_instantiate();
+
+        // Create two pools, one with stToken first, and the other with the stToken second
+        let pool1 = get_test_pool(1, "sttoken", AssetOrdering::StTokenFirst);
+        let pool2 = get_test_pool(2, "sttoken", AssetOrdering::NativeTokenFirst);
+
+        // Mock those two pools out in the query response
+        deps.querier.mock_stableswap_pool(1, &pool1);
+        deps.querier.mock_stableswap_pool(2, &pool2);
+
+        // Create the add messages, but swap the pool IDs (i.e. add_msg1 adds pool ID 2)
+        let add_msg1 = get_add_pool_msg(2, pool1.clone());
+        let add_msg2 = get_add_pool_msg(1, pool2.clone());
+
+        // Attempt to add these two pools, they should both fail since the asset ordering is incorrect
+        let add_resp1 = execute(deps.as_mut(), env.clone(), info.clone(), add_msg1);
+        assert_eq!(add_resp1, Err(ContractError::InvalidPoolAssetOrdering {}));
+
+        let add_resp2 = execute(deps.as_mut(), env.clone(), info.clone(), add_msg2);
+        assert_eq!(add_resp2, Err(ContractError::InvalidPoolAssetOrdering {}));
+    }
+
+    #[test]
+    fn test_scaling_factor_controller() {
+        let (mut deps, env, info) = default_instantiate();
+
+        // Register two pools controlled by the contract
+        for pool_id in [1, 2] {
+            let pool = get_test_pool(pool_id, "sttoken", AssetOrdering::StTokenFirst);
+            deps.querier.mock_stableswap_pool(pool_id, &pool);
+            let add_msg = get_add_pool_msg(pool_id, pool);
+            execute(deps.as_mut(), env.clone(), info.clone(), add_msg).unwrap();
+        }
+
+        // Attempt to add a pool that's controlled by a different address, it should fail
+        let pool_id = 3;
+        let pool = get_test_pool(pool_id, "sttoken", AssetOrdering::StTokenFirst);
+        deps.querier.mock_invalid_stableswap_pool(
+            pool_id,
+            StableswapPool {
+                id: pool_id,
+                pool_liquidity: vec![
+                    Coin {
+                        denom: "sttoken".to_string(),
+                        amount: "1000000".to_string(),
+                    },
+                    Coin {
+                        denom: "native_denom".to_string(),
+                        amount: "1000000".to_string(),
+                    },
+                ],
+                scaling_factor_controller: "other_controller".to_string(),
+                ..Default::default()
+            },
+        );
+        let add_msg = get_add_pool_msg(pool_id, pool);
+        let resp = execute(deps.as_mut(), env.clone(), info, add_msg);
+        assert_eq!(
+            resp,
+            Err(ContractError::NotScalingFactorController {
+                pool_id,
+                scaling_factor_controller: "other_controller".to_string(),
+            })
+        );
+
+        // Transfer control of pool 2 to a different address on Osmosis
+        deps.querier.mock_invalid_stableswap_pool(
+            2,
+            StableswapPool {
+                id: 2,
+                scaling_factor_controller: "other_controller".to_string(),
+                ..Default::default()
+            },
+        );
+
+        // The query should flag that pool 2 is no longer controlled by the contract
+        let query_msg = QueryMsg::ScalingFactorControllers {};
+        let query_resp = query(deps.as_ref(), env, query_msg).unwrap();
+        let controllers: ScalingFactorControllers = from_binary(&query_resp).unwrap();
+        assert_eq!(
+            controllers,
+            ScalingFactorControllers {
+                pools: vec![
+                    ScalingFactorControllerStatus {
+                        pool_id: 1,
+                        scaling_factor_controller: Some(MOCK_CONTRACT_ADDR.to_string()),
+                        is_controller: true,
+                    },
+                    ScalingFactorControllerStatus {
+                        pool_id: 2,
+                        scaling_factor_controller: Some("other_controller".to_string()),
+                        is_controller: false,
+                    },
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn test_add_pool_derived_asset_ordering() {
+        let (mut deps, env, info) = default_instantiate();
+
+        // Mock a pool with the stToken second
+        let pool_id = 1;
+        let pool = get_test_pool(pool_id, "sttoken", AssetOrdering::NativeTokenFirst);
+        deps.querier.mock_stableswap_pool(pool_id, &pool);
+
+        // Add the pool without specifying the ordering
+        let add_msg = ExecuteMsg::AddPool(AddPoolMsg {
+            pool_id,
+            sttoken_denom: "sttoken".to_string(),
+            asset_ordering: None,
+            asset_scaling_factors: None,
+            scaling_factor_multiplier: None,
+            rounding_mode: None,
+            asset_decimals: None,
+            ramp_duration_seconds: None,
+            deadband_bps: None,
+            deadband_max_age_seconds: None,
+            redemption_rate_offset_bps: None,
+            twap_window_seconds: None,
+            max_oracle_staleness_seconds: None,
+            min_update_interval_seconds: None,
+            circuit_breaker: None,
+            max_redemption_rate_decrease_bps: None,
+        });
+        let add_resp = execute(deps.as_mut(), env.clone(), info.clone(), add_msg).unwrap();
+        assert_eq!(
+            add_resp.attributes,
+            vec![
+                attr("action", "add_pool"),
+                attr("pool_id", "1"),
+                attr("pool_sttoken_denom", "sttoken"),
+                attr(
+                    "pool_asset_scaling_factors",
+                    "[native_anchor, oracle_driven]"
+                ),
+                attr("scaling_factor_multiplier", "100000"),
+                attr("rounding_mode", "floor"),
+            ]
+        );
+
+        // Confirm the derived ordering was stored
+        let query_resp = query(deps.as_ref(), env.clone(), QueryMsg::Pool { pool_id }).unwrap();
+        let queried_pool: Pool = from_binary(&query_resp).unwrap();
+        assert_eq!(queried_pool, pool);
+
+        // Attempt to add a pool with a denom that's not in the pool
+        let pool_id = 2;
+        let pool = get_test_pool(pool_id, "sttoken", AssetOrdering::StTokenFirst);
+        deps.querier.mock_stableswap_pool(pool_id, &pool);
+
+        let add_msg = ExecuteMsg::AddPool(AddPoolMsg {
+            pool_id,
+            sttoken_denom: "other_sttoken".to_string(),
+            asset_ordering: None,
+            asset_scaling_factors: None,
+            scaling_factor_multiplier: None,
+            rounding_mode: None,
+            asset_decimals: None,
+            ramp_duration_seconds: None,
+            deadband_bps: None,
+            deadband_max_age_seconds: None,
+            redemption_rate_offset_bps: None,
+            twap_window_seconds: None,
+            max_oracle_staleness_seconds: None,
+            min_update_interval_seconds: None,
+            circuit_breaker: None,
+            max_redemption_rate_decrease_bps: None,
+        });
+        let add_resp = execute(deps.as_mut(), env, info, add_msg);
+        assert_eq!(
+            add_resp,
+            Err(ContractError::DenomNotInPool {
+                pool_id,
+                denom: "other_sttoken".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_multi_asset_pool() {
+        let (mut deps, mut env, info) = default_instantiate();
+
+        // Mock a three asset pool with the stToken second
+        let pool_id = 1;
+        let sttoken_denom = "sttoken";
+        let pool = Pool {
+            asset_scaling_factors: vec![
+                AssetScalingFactor::NativeAnchor {},
+                AssetScalingFactor::OracleDriven {},
+                AssetScalingFactor::Fixed {
+                    scaling_factor: 100000,
+                },
+            ],
+            ..get_test_pool(pool_id, sttoken_denom, AssetOrdering::NativeTokenFirst)
+        };
+        deps.querier.mock_stableswap_pool(pool_id, &pool);
+
+        // Attempting to add the pool with only an asset ordering should fail
+        let add_msg = ExecuteMsg::AddPool(AddPoolMsg {
+            pool_id,
+            sttoken_denom: sttoken_denom.to_string(),
+            asset_ordering: Some(AssetOrdering::NativeTokenFirst),
+            asset_scaling_factors: None,
+            scaling_factor_multiplier: None,
+            rounding_mode: None,
+            asset_decimals: None,
+            ramp_duration_seconds: None,
+            deadband_bps: None,
+            deadband_max_age_seconds: None,
+            redemption_rate_offset_bps: None,
+            twap_window_seconds: None,
+            max_oracle_staleness_seconds: None,
+            min_update_interval_seconds: None,
+            circuit_breaker: None,
+            max_redemption_rate_decrease_bps: None,
+        });
+        let add_resp = execute(deps.as_mut(), env.clone(), info.clone(), add_msg);
+        assert_eq!(
+            add_resp,
+            Err(ContractError::InvalidNumberOfPoolAssets { number: 3 })
+        );
+
+        // Attempting to add the pool without a descriptor for each asset should fail
+        let add_msg = ExecuteMsg::AddPool(AddPoolMsg {
+            pool_id,
+            sttoken_denom: sttoken_denom.to_string(),
+            asset_ordering: None,
+            asset_scaling_factors: Some(AssetOrdering::NativeTokenFirst.asset_scaling_factors()),
+            scaling_factor_multiplier: None,
+            rounding_mode: None,
+            asset_decimals: None,
+            ramp_duration_seconds: None,
+            deadband_bps: None,
+            deadband_max_age_seconds: None,
+            redemption_rate_offset_bps: None,
+            twap_window_seconds: None,
+            max_oracle_staleness_seconds: None,
+            min_update_interval_seconds: None,
+            circuit_breaker: None,
+            max_redemption_rate_decrease_bps: None,
+        });
+        let add_resp = execute(deps.as_mut(), env.clone(), info.clone(), add_msg);
+        assert_eq!(
+            add_resp,
+            Err(ContractError::InvalidNumberOfAssetScalingFactors {
+                number: 2,
+                expected: 3
+            })
+        );
+
+        // Add the pool with the full set of descriptors
+        let add_msg = get_add_pool_msg(pool_id, pool.clone());
+        let add_resp = execute(deps.as_mut(), env.clone(), info, add_msg).unwrap();
+        assert_eq!(
+            add_resp.attributes,
+            vec![
+                attr("action", "add_pool"),
+                attr("pool_id", "1"),
+                attr("pool_sttoken_denom", "sttoken"),
+                attr(
+                    "pool_asset_scaling_factors",
+                    "[native_anchor, oracle_driven, fixed(100000)]"
+                ),
+                attr("scaling_factor_multiplier", "100000"),
+                attr("rounding_mode", "floor"),
+            ]
+        );
+
+        // Update the scaling factors, only the native token's factor should be scaled
+        let update_resp =
+            update_scaling_factor_at(&mut deps, &mut env, pool_id, sttoken_denom, "1.2", 1_000)
+                .unwrap();
+        let expected_update_msg: CosmosMsg = MsgStableSwapAdjustScalingFactors {
+            sender: env.contract.address.to_string(),
+            pool_id,
+            scaling_factors: vec![120000, 100000, 100000],
+        }
+        .into();
+        assert_eq!(update_resp.messages.len(), 1);
+        assert_eq!(update_resp.messages[0].msg, expected_update_msg);
+    }
+
+    #[test]
+    fn test_paired_sttoken_pool() {
+        let (mut deps, mut env, info) = default_instantiate();
+
+        // Mock a stATOM/stkATOM pool, where the stkATOM redemption rate is in a different oracle
+        let pool_id = 1;
+        let sttoken_denom = "statom";
+        let paired_denom = "stkatom";
+        let pool = Pool {
+            asset_scaling_factors: vec![
+                AssetScalingFactor::OracleDriven {},
+                AssetScalingFactor::PairedStToken {
+                    denom: paired_denom.to_string(),
+                    oracle_contract_address: Some(PAIRED_ORACLE_ADDRESS.to_string()),
+                },
+            ],
+            ..get_test_pool(pool_id, sttoken_denom, AssetOrdering::StTokenFirst)
+        };
+        deps.querier.mock_stableswap_pool(pool_id, &pool);
+
+        // Attempting to add the pool with a paired denom that doesn't match the pool should fail
+        let add_msg = ExecuteMsg::AddPool(AddPoolMsg {
+            pool_id,
+            sttoken_denom: sttoken_denom.to_string(),
+            asset_ordering: None,
+            asset_scaling_factors: Some(vec![
+                AssetScalingFactor::OracleDriven {},
+                AssetScalingFactor::PairedStToken {
+                    denom: "other_denom".to_string(),
+                    oracle_contract_address: None,
+                },
+            ]),
+            scaling_factor_multiplier: None,
+            rounding_mode: None,
+            asset_decimals: None,
+            ramp_duration_seconds: None,
+            deadband_bps: None,
+            deadband_max_age_seconds: None,
+            redemption_rate_offset_bps: None,
+            twap_window_seconds: None,
+            max_oracle_staleness_seconds: None,
+            min_update_interval_seconds: None,
+            circuit_breaker: None,
+            max_redemption_rate_decrease_bps: None,
+        });
+        let add_resp = execute(deps.as_mut(), env.clone(), info.clone(), add_msg);
+        assert_eq!(
+            add_resp,
+            Err(ContractError::InvalidAssetScalingFactor { index: 1 })
+        );
+
+        // Add the pool
+        let add_msg = get_add_pool_msg(pool_id, pool.clone());
+        let add_resp = execute(deps.as_mut(), env.clone(), info.clone(), add_msg).unwrap();
+        assert_eq!(
+            add_resp.attributes,
+            vec![
+                attr("action", "add_pool"),
+                attr("pool_id", "1"),
+                attr("pool_sttoken_denom", "statom"),
+                attr(
+                    "pool_asset_scaling_factors",
+                    "[oracle_driven, paired_sttoken(stkatom)]"
+                ),
+                attr("scaling_factor_multiplier", "100000"),
+                attr("rounding_mode", "floor"),
+            ]
+        );
+
+        // Attempt to update the pool before the paired redemption rate is in the oracle,
+        // it should fail
+        let block_time = 1_000_000;
+        let resp = update_scaling_factor_at(
+            &mut deps,
+            &mut env,
+            pool_id,
+            sttoken_denom,
+            "1.2",
+            block_time,
+        );
+        assert_eq!(
+            resp,
+            Err(ContractError::UnableToQueryRedemptionRate {
+                token: paired_denom.to_string(),
+                error: "Generic error: Querier system error: Unknown system error".to_string(),
+            })
+        );
+
+        // Mock a stale paired redemption rate, the update should be rejected
+        deps.querier.mock_redemption_rate_for_oracle(
+            PAIRED_ORACLE_ADDRESS,
+            paired_denom.to_string(),
+            Decimal::from_str("1.1").unwrap(),
+            block_time - MAX_ORACLE_STALENESS_SECONDS - 1,
+        );
+        let resp = update_scaling_factor_at(
+            &mut deps,
+            &mut env,
+            pool_id,
+            sttoken_denom,
+            "1.2",
+            block_time,
+        );
+        assert_eq!(
+            resp,
+            Err(ContractError::StaleRedemptionRate {
+                token: paired_denom.to_string(),
+                age: MAX_ORACLE_STALENESS_SECONDS + 1,
+                max_staleness: MAX_ORACLE_STALENESS_SECONDS,
+            })
+        );
+
+        // Update the paired redemption rate, the stkATOM scaling factor should be
+        // scaled by the ratio of the two redemption rates (1.2 / 1.1)
+        deps.querier.mock_redemption_rate_for_oracle(
+            PAIRED_ORACLE_ADDRESS,
+            paired_denom.to_string(),
+            Decimal::from_str("1.1").unwrap(),
+            block_time,
+        );
+        let update_resp = update_scaling_factor_at(
+            &mut deps,
+            &mut env,
+            pool_id,
+            sttoken_denom,
+            "1.2",
+            block_time,
+        )
+        .unwrap();
+        assert_eq!(
+            update_resp.attributes,
+            vec![
+                attr("action", "update_scaling_factor"),
+                attr("pool_id", "1"),
+                attr("redemption_rate", "1.2"),
+                attr("paired_redemption_rates", "[stkatom: 1.1]"),
+                attr("scaling_factors", "[100000, 109090]"),
+            ]
+        );
+
+        // Register a second oracle and require both oracles to agree
+        let add_source_msg = ExecuteMsg::AddOracleSource {
+            contract_address: SECONDARY_ORACLE_ADDRESS.to_string(),
+            adapter: OracleAdapter::IcaOracle {},
+        };
+        execute(deps.as_mut(), env.clone(), info.clone(), add_source_msg).unwrap();
+        let update_config_msg = ExecuteMsg::UpdateConfig(UpdateConfigMsg {
+            oracle_quorum: Some(2),
+            ..Default::default()
+        });
+        execute(deps.as_mut(), env.clone(), info, update_config_msg).unwrap();
+
+        // The paired redemption rate is only in the pool's oracle, so the quorum isn't met
+        let block_time = block_time + 100;
+        deps.querier.mock_redemption_rate_for_oracle(
+            SECONDARY_ORACLE_ADDRESS,
+            sttoken_denom.to_string(),
+            Decimal::from_str("1.2").unwrap(),
+            block_time,
+        );
+        deps.querier.mock_redemption_rate_for_oracle(
+            PAIRED_ORACLE_ADDRESS,
+            paired_denom.to_string(),
+            Decimal::from_str("1.1").unwrap(),
+            block_time,
+        );
+        let resp = update_scaling_factor_at(
+            &mut deps,
+            &mut env,
+            pool_id,
+            sttoken_denom,
+            "1.2",
+            block_time,
+        );
+        assert_eq!(
+            resp,
+            Err(ContractError::OracleQuorumNotMet {
+                token: paired_denom.to_string(),
+                responses: 1,
+                quorum: 2,
+            })
+        );
+
+        // Once the second oracle has the paired redemption rate, the median of the pool's
+        // oracle and the second oracle should be applied
+        deps.querier.mock_redemption_rate_for_oracle(
+            SECONDARY_ORACLE_ADDRESS,
+            paired_denom.to_string(),
+            Decimal::from_str("1.12").unwrap(),
+            block_time,
+        );
+        let update_resp = update_scaling_factor_at(
+            &mut deps,
+            &mut env,
+            pool_id,
+            sttoken_denom,
+            "1.2",
+            block_time,
+        )
+        .unwrap();
+        assert_eq!(
+            update_resp.attributes,
+            vec![
+                attr("action", "update_scaling_factor"),
+                attr("pool_id", "1"),
+                attr("redemption_rate", "1.2"),
+                attr(
+                    "oracle_redemption_rates",
+                    "statom: [oracle: 1.2, secondary_oracle: 1.2]"
+                ),
+                attr(
+                    "oracle_redemption_rates",
+                    "stkatom: [paired_oracle: 1.1, secondary_oracle: 1.12]"
+                ),
+                attr("paired_redemption_rates", "[stkatom: 1.11]"),
+                attr("scaling_factors", "[100000, 108108]"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scaling_factor_multiplier() {
+        let (mut deps, mut env, info) = default_instantiate();
+
+        let pool_id = 1;
+        let sttoken_denom = "sttoken";
+        let pool = Pool {
+            scaling_factor_multiplier: 100_000_000,
+            ..get_test_pool(pool_id, sttoken_denom, AssetOrdering::StTokenFirst)
+        };
+        deps.querier.mock_stableswap_pool(pool_id, &pool);
+
+        // Attempt to add the pool with a multiplier that's zero or could overflow, it should fail
+        for multiplier in [0, u64::MAX] {
+            let add_msg = get_add_pool_msg(
+                pool_id,
+                Pool {
+                    scaling_factor_multiplier: multiplier,
+                    ..pool.clone()
+                },
+            );
+            let add_resp = execute(deps.as_mut(), env.clone(), info.clone(), add_msg);
+            assert_eq!(
+                add_resp,
+                Err(ContractError::InvalidScalingFactorMultiplier {
+                    multiplier,
+                    max_multiplier: u64::MAX / 100,
+                })
+            );
+        }
+
+        // Add the pool with eight decimals of precision
+        let add_msg = get_add_pool_msg(pool_id, pool);
+        execute(deps.as_mut(), env.clone(), info, add_msg).unwrap();
+
+        // Update the scaling factors, the redemption rate should be truncated to eight decimals
+        let update_resp = update_scaling_factor_at(
+            &mut deps,
+            &mut env,
+            pool_id,
+            sttoken_denom,
+            "1.234567891",
+            1_000,
+        )
+        .unwrap();
+        assert_eq!(
+            update_resp.attributes,
+            vec![
+                attr("action", "update_scaling_factor"),
+                attr("pool_id", "1"),
+                attr("redemption_rate", "1.234567891"),
+                attr("scaling_factors", "[100000000, 123456789]"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rounding_mode() {
+        let (mut deps, mut env, info) = default_instantiate();
+
+        // Add a pool that rounds the scaling factors up
+        let pool_id = 1;
+        let sttoken_denom = "sttoken";
+        let pool = Pool {
+            rounding_mode: RoundingMode::Ceil,
+            ..get_test_pool(pool_id, sttoken_denom, AssetOrdering::StTokenFirst)
+        };
+        deps.querier.mock_stableswap_pool(pool_id, &pool);
+        let add_msg = get_add_pool_msg(pool_id, pool);
+        execute(deps.as_mut(), env.clone(), info.clone(), add_msg).unwrap();
+
+        let update_resp = update_scaling_factor_at(
+            &mut deps,
+            &mut env,
+            pool_id,
+            sttoken_denom,
+            "1.234561",
+            1_000,
+        )
+        .unwrap();
+        assert_eq!(
+            update_resp.attributes[3],
+            attr("scaling_factors", "[100000, 123457]")
+        );
+
+        // Switch the pool to round to the nearest even integer
+        let update_pool_msg = ExecuteMsg::UpdatePool(UpdatePoolMsg {
+            pool_id,
+            rounding_mode: Some(RoundingMode::HalfEven),
+            ..Default::default()
+        });
+        let update_pool_resp = execute(deps.as_mut(), env.clone(), info, update_pool_msg).unwrap();
+        assert_eq!(
+            update_pool_resp.attributes,
+            vec![
+                attr("action", "update_pool"),
+                attr("pool_id", "1"),
+                attr("rounding_mode", "half_even"),
+            ]
+        );
+
+        // A redemption rate at the midpoint should round to the even scaling factor
+        let update_resp = update_scaling_factor_at(
+            &mut deps,
+            &mut env,
+            pool_id,
+            sttoken_denom,
+            "1.234565",
+            2_000,
+        )
+        .unwrap();
+        assert_eq!(
+            update_resp.attributes[3],
+            attr("scaling_factors", "[100000, 123456]")
+        );
+    }
+
+    #[test]
+    fn test_asset_decimals() {
+        let (mut deps, mut env, info) = default_instantiate();
+
+        // Mock a pool with a 6 decimal stToken and an 18 decimal native token
+        let pool_id = 1;
+        let sttoken_denom = "sttoken";
+        let pool = Pool {
+            asset_decimals: Some(vec![6, 18]),
+            ..get_test_pool(pool_id, sttoken_denom, AssetOrdering::StTokenFirst)
+        };
+        deps.querier.mock_stableswap_pool(pool_id, &pool);
+
+        // Attempt to add the pool without decimals for each asset, it should fail
+        let add_msg = get_add_pool_msg(
+            pool_id,
+            Pool {
+                asset_decimals: Some(vec![6]),
+                ..pool.clone()
+            },
+        );
+        let add_resp = execute(deps.as_mut(), env.clone(), info.clone(), add_msg);
+        assert_eq!(
+            add_resp,
+            Err(ContractError::InvalidNumberOfAssetDecimals {
+                number: 1,
+                expected: 2
+            })
+        );
+
+        // Attempt to add the pool with a difference in decimals that could overflow
+        let add_msg = get_add_pool_msg(
+            pool_id,
+            Pool {
+                asset_decimals: Some(vec![0, 18]),
+                ..pool.clone()
+            },
+        );
+        let add_resp = execute(deps.as_mut(), env.clone(), info.clone(), add_msg);
+        assert_eq!(
+            add_resp,
+            Err(ContractError::InvalidAssetDecimals {
+                decimal_difference: 18
+            })
+        );
+
+        // Add the pool
+        let add_msg = get_add_pool_msg(pool_id, pool);
+        let add_resp = execute(deps.as_mut(), env.clone(), info, add_msg).unwrap();
+        assert!(add_resp
+            .attributes
+            .contains(&attr("asset_decimals", "[6, 18]")));
+
+        // Update the scaling factors, the native token's scaling factor should include
+        // the 12 decimal difference
+        let update_resp =
+            update_scaling_factor_at(&mut deps, &mut env, pool_id, sttoken_denom, "1.2", 1_000)
+                .unwrap();
+        let expected_update_msg: CosmosMsg = MsgStableSwapAdjustScalingFactors {
+            sender: env.contract.address.to_string(),
+            pool_id,
+            scaling_factors: vec![100000, 120000000000000000],
+        }
+        .into();
+        assert_eq!(update_resp.messages.len(), 1);
+        assert_eq!(update_resp.messages[0].msg, expected_update_msg);
+    }
+
+    #[test]
+    fn test_scaling_factor_ramp() {
+        let (mut deps, mut env, info) = default_instantiate();
+
+        // Add a pool that ramps its scaling factors over 1000 seconds
+        let pool_id = 1;
+        let sttoken_denom = "sttoken";
+        let pool = Pool {
+            ramp_duration_seconds: Some(1000),
+            ..get_test_pool(pool_id, sttoken_denom, AssetOrdering::StTokenFirst)
+        };
+        deps.querier.mock_stableswap_pool(pool_id, &pool);
+        let add_msg = get_add_pool_msg(pool_id, pool);
+        execute(deps.as_mut(), env.clone(), info.clone(), add_msg).unwrap();
+
+        // The first update should apply the scaling factors directly since there's
+        // nothing to ramp from
+        let update_resp =
+            update_scaling_factor_at(&mut deps, &mut env, pool_id, sttoken_denom, "1.2", 1_000)
+                .unwrap();
+        assert_eq!(update_resp.messages.len(), 1);
+
+        // Update with a new redemption rate, it should start a ramp without changing the
+        // scaling factors
+        let update_resp =
+            update_scaling_factor_at(&mut deps, &mut env, pool_id, sttoken_denom, "1.4", 2_000)
+                .unwrap();
+        assert_eq!(
+            update_resp.attributes,
+            vec![
+                attr("action", "update_scaling_factor"),
+                attr("pool_id", "1"),
+                attr("redemption_rate", "1.4"),
+                attr("target_scaling_factors", "[100000, 140000]"),
+                attr("ramp_end_time", "3000"),
+                attr("ramp", "started"),
+            ]
+        );
+        assert_eq!(update_resp.messages.len(), 0);
+
+        let query_msg = QueryMsg::ScalingFactorRamp { pool_id };
+        let query_resp = query(deps.as_ref(), env.clone(), query_msg.clone()).unwrap();
+        let ramp_status: ScalingFactorRampStatus = from_binary(&query_resp).unwrap();
+        assert_eq!(
+            ramp_status,
+            ScalingFactorRampStatus {
+                current_scaling_factors: vec![100000, 120000],
+                target_scaling_factors: vec![100000, 140000],
+                ramp_end_time: Some(3000),
+            }
+        );
+
+        // Halfway through the ramp, the scaling factors should be halfway to the target
+        let update_resp =
+            update_scaling_factor_at(&mut deps, &mut env, pool_id, sttoken_denom, "1.4", 2_500)
+                .unwrap();
+        assert_eq!(
+            update_resp.attributes,
+            vec![
+                attr("action", "update_scaling_factor"),
+                attr("pool_id", "1"),
+                attr("redemption_rate", "1.4"),
+                attr("target_scaling_factors", "[100000, 140000]"),
+                attr("ramp_end_time", "3000"),
+                attr("scaling_factors", "[100000, 130000]"),
+            ]
+        );
+        assert_eq!(update_resp.messages.len(), 1);
+
+        // At the end of the ramp, the scaling factors should reach the target
+        // and the ramp should be cleared
+        let update_resp =
+            update_scaling_factor_at(&mut deps, &mut env, pool_id, sttoken_denom, "1.4", 3_500)
+                .unwrap();
+        assert_eq!(
+            update_resp.attributes.last(),
+            Some(&attr("scaling_factors", "[100000, 140000]"))
+        );
+
+        let query_resp = query(deps.as_ref(), env.clone(), query_msg.clone()).unwrap();
+        let ramp_status: ScalingFactorRampStatus = from_binary(&query_resp).unwrap();
+        assert_eq!(
+            ramp_status,
+            ScalingFactorRampStatus {
+                current_scaling_factors: vec![100000, 140000],
+                target_scaling_factors: vec![100000, 140000],
+                ramp_end_time: None,
+            }
+        );
+
+        // Start another ramp, and then disable ramp mode, which should cancel the ramp
+        update_scaling_factor_at(&mut deps, &mut env, pool_id, sttoken_denom, "1.5", 4_000)
+            .unwrap();
+        let update_pool_msg = ExecuteMsg::UpdatePool(UpdatePoolMsg {
+            pool_id,
+            ramp_duration_seconds: Some(0),
+            ..Default::default()
+        });
+        execute(deps.as_mut(), env.clone(), info, update_pool_msg).unwrap();
+
+        let query_resp = query(deps.as_ref(), env.clone(), query_msg).unwrap();
+        let ramp_status: ScalingFactorRampStatus = from_binary(&query_resp).unwrap();
+        assert_eq!(ramp_status.ramp_end_time, None);
+
+        // The next update should apply the target directly
+        let update_resp =
+            update_scaling_factor_at(&mut deps, &mut env, pool_id, sttoken_denom, "1.5", 4_100)
+                .unwrap();
+        assert_eq!(
+            update_resp.attributes.last(),
+            Some(&attr("scaling_factors", "[100000, 150000]"))
+        );
+    }
+
+    #[test]
+    fn test_scaling_factor_ramp_changing_target() {
+        let (mut deps, mut env, info) = default_instantiate();
+
+        // Add a pool that ramps its scaling factors over 1000 seconds
+        let pool_id = 1;
+        let sttoken_denom = "sttoken";
+        let pool = Pool {
+            ramp_duration_seconds: Some(1000),
+            ..get_test_pool(pool_id, sttoken_denom, AssetOrdering::StTokenFirst)
+        };
+        deps.querier.mock_stableswap_pool(pool_id, &pool);
+        let add_msg = get_add_pool_msg(pool_id, pool);
+        execute(deps.as_mut(), env.clone(), info, add_msg).unwrap();
+
+        // Apply the initial scaling factors and start a ramp toward 1.4
+        update_scaling_factor_at(&mut deps, &mut env, pool_id, sttoken_denom, "1.2", 1_000)
+            .unwrap();
+        update_scaling_factor_at(&mut deps, &mut env, pool_id, sttoken_denom, "1.4", 2_000)
+            .unwrap();
+
+        // Change the redemption rate on every update, each update should pick up from the
+        // current position of the ramp and keep the original end time, so the scaling
+        // factors keep advancing rather than being held at the start of a new ramp
+        // Once the original end time has passed, a new ramp is started from the target
+        let updates = vec![
+            ("1.41", 2_250, "[100000, 141000]", 3_000, "[100000, 125000]"),
+            ("1.42", 2_500, "[100000, 142000]", 3_000, "[100000, 130333]"),
+            ("1.43", 2_750, "[100000, 143000]", 3_000, "[100000, 136166]"),
+            ("1.44", 3_000, "[100000, 144000]", 4_000, "[100000, 143000]"),
+        ];
+        for (redemption_rate, block_time, target, ramp_end_time, scaling_factors) in updates {
+            let update_resp = update_scaling_factor_at(
+                &mut deps,
+                &mut env,
+                pool_id,
+                sttoken_denom,
+                redemption_rate,
+                block_time,
+            )
+            .unwrap();
+            assert_eq!(
+                update_resp.attributes,
+                vec![
+                    attr("action", "update_scaling_factor"),
+                    attr("pool_id", "1"),
+                    attr("redemption_rate", redemption_rate),
+                    attr("target_scaling_factors", target),
+                    attr("ramp_end_time", ramp_end_time.to_string()),
+                    attr("scaling_factors", scaling_factors),
+                ]
+            );
+            assert_eq!(update_resp.messages.len(), 1);
+        }
+
+        let query_msg = QueryMsg::ScalingFactorRamp { pool_id };
+        let query_resp = query(deps.as_ref(), env, query_msg).unwrap();
+        let ramp_status: ScalingFactorRampStatus = from_binary(&query_resp).unwrap();
+        assert_eq!(
+            ramp_status,
+            ScalingFactorRampStatus {
+                current_scaling_factors: vec![100000, 143000],
+                target_scaling_factors: vec![100000, 144000],
+                ramp_end_time: Some(4_000),
+            }
+        );
+    }
+
+    #[test]
+    fn test_update_scaling_factor_deadband() {
+        let (mut deps, mut env, info) = default_instantiate();
+
+        // Add a pool with a 10 bps deadband, that's bypassed if the pool
+        // hasn't been updated in a day
+        let pool_id = 1;
+        let sttoken_denom = "sttoken";
+        let pool = Pool {
+            deadband_bps: Some(10),
+            deadband_max_age_seconds: Some(86_400),
+            ..get_test_pool(pool_id, sttoken_denom, AssetOrdering::StTokenFirst)
+        };
+        deps.querier.mock_stableswap_pool(pool_id, &pool);
+        let add_msg = get_add_pool_msg(pool_id, pool);
+        execute(deps.as_mut(), env.clone(), info, add_msg).unwrap();
+
+        // The first update should always be applied
+        let block_time = 1_000_000;
+        let update_resp = update_scaling_factor_at(
+            &mut deps,
+            &mut env,
+            pool_id,
+            sttoken_denom,
+            "1.2",
+            block_time,
+        )
+        .unwrap();
+        assert_eq!(update_resp.messages.len(), 1);
+
+        // A small increase in the redemption rate is within the deadband and should be skipped
+        let update_resp = update_scaling_factor_at(
+            &mut deps,
+            &mut env,
+            pool_id,
+            sttoken_denom,
+            "1.2001",
+            block_time + 100,
+        )
+        .unwrap();
+        assert_eq!(
+            update_resp.attributes,
+            vec![
+                attr("action", "update_scaling_factor"),
+                attr("pool_id", "1"),
+                attr("redemption_rate", "1.2001"),
+                attr("reason", "within_deadband"),
+            ]
+        );
+        assert_eq!(update_resp.messages.len(), 0);
+
+        // A larger increase should be applied
+        let update_resp = update_scaling_factor_at(
+            &mut deps,
+            &mut env,
+            pool_id,
+            sttoken_denom,
+            "1.21",
+            block_time + 200,
+        )
+        .unwrap();
+        assert_eq!(
+            update_resp.attributes.last(),
+            Some(&attr("scaling_factors", "[100000, 121000]"))
+        );
+
+        // Once the max age has passed, a small increase should be applied
+        let update_resp = update_scaling_factor_at(
+            &mut deps,
+            &mut env,
+            pool_id,
+            sttoken_denom,
+            "1.2101",
+            block_time + 200 + 86_400,
+        )
+        .unwrap();
+        assert_eq!(
+            update_resp.attributes.last(),
+            Some(&attr("scaling_factors", "[100000, 121010]"))
+        );
+    }
+
+    #[test]
+    fn test_redemption_rate_offset() {
+        let (mut deps, mut env, info) = default_instantiate();
+
+        // Add a pool with a 0.5% premium on the redemption rate
+        let pool_id = 1;
+        let sttoken_denom = "sttoken";
+        let pool = Pool {
+            redemption_rate_offset_bps: Some(50),
+            ..get_test_pool(pool_id, sttoken_denom, AssetOrdering::StTokenFirst)
+        };
+        deps.querier.mock_stableswap_pool(pool_id, &pool);
+        let add_msg = get_add_pool_msg(pool_id, pool);
+        let add_resp = execute(deps.as_mut(), env.clone(), info.clone(), add_msg).unwrap();
+        assert!(add_resp
+            .attributes
+            .contains(&attr("redemption_rate_offset_bps", "50")));
+
+        // The scaling factors should be built from the adjusted redemption rate,
+        // and both the raw and adjusted rates should be emitted
+        let block_time = 1_000_000;
+        let update_resp = update_scaling_factor_at(
+            &mut deps,
+            &mut env,
+            pool_id,
+            sttoken_denom,
+            "1.2",
+            block_time,
+        )
+        .unwrap();
+        assert_eq!(
+            update_resp.attributes,
+            vec![
+                attr("action", "update_scaling_factor"),
+                attr("pool_id", "1"),
+                attr("redemption_rate", "1.2"),
+                attr("redemption_rate_offset_bps", "50"),
+                attr("adjusted_redemption_rate", "1.206"),
+                attr("scaling_factors", "[100000, 120600]"),
+            ]
+        );
+
+        // The raw redemption rate should be recorded on the pool
+        let query_resp = query(deps.as_ref(), env.clone(), QueryMsg::Pool { pool_id }).unwrap();
+        let pool: Pool = from_binary(&query_resp).unwrap();
+        assert_eq!(
+            pool.last_redemption_rate,
+            Some(Decimal::from_str("1.2").unwrap())
+        );
+
+        // An offset beyond the config's max (in either direction) should be rejected
+        let update_pool_msg = ExecuteMsg::UpdatePool(UpdatePoolMsg {
+            pool_id,
+            redemption_rate_offset_bps: Some(-101),
+            ..Default::default()
+        });
+        let update_pool_resp = execute(deps.as_mut(), env.clone(), info.clone(), update_pool_msg);
+        assert_eq!(
+            update_pool_resp,
+            Err(ContractError::RedemptionRateOffsetExceedsMax {
+                offset_bps: -101,
+                max_offset_bps: MAX_REDEMPTION_RATE_OFFSET_BPS,
+            })
+        );
+
+        // Switch to a 1% discount
+        let update_pool_msg = ExecuteMsg::UpdatePool(UpdatePoolMsg {
+            pool_id,
+            redemption_rate_offset_bps: Some(-100),
+            ..Default::default()
+        });
+        execute(deps.as_mut(), env.clone(), info.clone(), update_pool_msg).unwrap();
+
+        let update_resp = update_scaling_factor_at(
+            &mut deps,
+            &mut env,
+            pool_id,
+            sttoken_denom,
+            "1.2",
+            block_time + 100,
+        )
+        .unwrap();
+        assert_eq!(
+            update_resp.attributes.last(),
+            Some(&attr("scaling_factors", "[100000, 118800]"))
+        );
+
+        // If the config's max is lowered below the pool's offset, the offset is bounded
+        // by the new max
+        let update_config_msg = ExecuteMsg::UpdateConfig(UpdateConfigMsg {
+            max_redemption_rate_offset_bps: Some(50),
+            ..Default::default()
+        });
+        execute(deps.as_mut(), env.clone(), info, update_config_msg).unwrap();
+
+        let update_resp = update_scaling_factor_at(
+            &mut deps,
+            &mut env,
+            pool_id,
+            sttoken_denom,
+            "1.2",
+            block_time + 200,
+        )
+        .unwrap();
+        assert_eq!(
+            update_resp.attributes,
+            vec![
+                attr("action", "update_scaling_factor"),
+                attr("pool_id", "1"),
+                attr("redemption_rate", "1.2"),
+                attr("redemption_rate_offset_bps", "-50"),
+                attr("adjusted_redemption_rate", "1.194"),
+                attr("scaling_factors", "[100000, 119400]"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_redemption_rate_twap() {
+        let (mut deps, mut env, info) = default_instantiate();
+
+        // Add a pool that applies the average redemption rate over the last hour
+        let pool_id = 1;
+        let sttoken_denom = "sttoken";
+        let pool = Pool {
+            twap_window_seconds: Some(3600),
+            ..get_test_pool(pool_id, sttoken_denom, AssetOrdering::StTokenFirst)
+        };
+        deps.querier.mock_stableswap_pool(pool_id, &pool);
+        let add_msg = get_add_pool_msg(pool_id, pool);
+        let add_resp = execute(deps.as_mut(), env.clone(), info.clone(), add_msg).unwrap();
+        assert!(add_resp
+            .attributes
+            .contains(&attr("twap_window_seconds", "3600")));
+
+        // With only a single observation, the average is the spot redemption rate
+        let block_time = 1_000_000;
+        let update_resp = update_scaling_factor_at(
+            &mut deps,
+            &mut env,
+            pool_id,
+            sttoken_denom,
+            "1.2",
+            block_time,
+        )
+        .unwrap();
+        assert_eq!(
+            update_resp.attributes,
+            vec![
+                attr("action", "update_scaling_factor"),
+                attr("pool_id", "1"),
+                attr("redemption_rate", "1.2"),
+                attr("twap_redemption_rate", "1.2"),
+                attr("scaling_factors", "[100000, 120000]"),
+            ]
+        );
+
+        // Halfway through the window, the new redemption rate has not yet been in effect,
+        // so the average is unchanged and the update is skipped
+        let update_resp = update_scaling_factor_at(
+            &mut deps,
+            &mut env,
+            pool_id,
+            sttoken_denom,
+            "1.3",
+            block_time + 1800,
+        )
+        .unwrap();
+        assert_eq!(
+            update_resp.attributes,
+            vec![
+                attr("action", "update_scaling_factor"),
+                attr("pool_id", "1"),
+                attr("redemption_rate", "1.3"),
+                attr("twap_redemption_rate", "1.2"),
+                attr("reason", "scaling_factors_unchanged"),
+            ]
+        );
+        assert_eq!(update_resp.messages.len(), 0);
+
+        // Once each redemption rate has been in effect for half the window,
+        // the average should be the midpoint
+        let update_resp = update_scaling_factor_at(
+            &mut deps,
+            &mut env,
+            pool_id,
+            sttoken_denom,
+            "1.3",
+            block_time + 3600,
+        )
+        .unwrap();
+        assert_eq!(
+            update_resp.attributes,
+            vec![
+                attr("action", "update_scaling_factor"),
+                attr("pool_id", "1"),
+                attr("redemption_rate", "1.3"),
+                attr("twap_redemption_rate", "1.25"),
+                attr("scaling_factors", "[100000, 125000]"),
+            ]
+        );
+
+        // The average redemption rate should be recorded on the pool
+        let query_resp = query(deps.as_ref(), env.clone(), QueryMsg::Pool { pool_id }).unwrap();
+        let pool: Pool = from_binary(&query_resp).unwrap();
+        assert_eq!(
+            pool.last_redemption_rate,
+            Some(Decimal::from_str("1.25").unwrap())
+        );
+
+        // Each observed redemption rate should be returned from the history query
+        let expected_samples = vec![
+            RedemptionRateSample {
+                update_time: block_time,
+                redemption_rate: Decimal::from_str("1.2").unwrap(),
+            },
+            RedemptionRateSample {
+                update_time: block_time + 1800,
+                redemption_rate: Decimal::from_str("1.3").unwrap(),
+            },
+            RedemptionRateSample {
+                update_time: block_time + 3600,
+                redemption_rate: Decimal::from_str("1.3").unwrap(),
+            },
+        ];
+        let history_msg = QueryMsg::RedemptionRateHistory {
+            denom: sttoken_denom.to_string(),
+            window_seconds: Some(3600),
+        };
+        let query_resp = query(deps.as_ref(), env.clone(), history_msg).unwrap();
+        let history: RedemptionRateHistory = from_binary(&query_resp).unwrap();
+        assert_eq!(
+            history,
+            RedemptionRateHistory {
+                samples: expected_samples,
+                time_weighted_average: Some(Decimal::from_str("1.25").unwrap()),
+            }
+        );
+
+        // Disabling the window should apply the spot redemption rate
+        let update_pool_msg = ExecuteMsg::UpdatePool(UpdatePoolMsg {
+            pool_id,
+            twap_window_seconds: Some(0),
+            ..Default::default()
+        });
+        execute(deps.as_mut(), env.clone(), info.clone(), update_pool_msg).unwrap();
+
+        let update_resp = update_scaling_factor_at(
+            &mut deps,
+            &mut env,
+            pool_id,
+            sttoken_denom,
+            "1.3",
+            block_time + 3700,
+        )
+        .unwrap();
+        assert_eq!(
+            update_resp.attributes,
+            vec![
+                attr("action", "update_scaling_factor"),
+                attr("pool_id", "1"),
+                attr("redemption_rate", "1.3"),
+                attr("scaling_factors", "[100000, 130000]"),
+            ]
+        );
+
+        // A denom that has never been observed has an empty history
+        let history_msg = QueryMsg::RedemptionRateHistory {
+            denom: "unknown".to_string(),
+            window_seconds: None,
+        };
+        let query_resp = query(deps.as_ref(), env.clone(), history_msg).unwrap();
+        let history: RedemptionRateHistory = from_binary(&query_resp).unwrap();
+        assert_eq!(
+            history,
+            RedemptionRateHistory {
+                samples: vec![],
+                time_weighted_average: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_oracle_sources() {
+        let (mut deps, mut env, info) = default_instantiate();
+
+        let pool_id = 1;
+        let sttoken_denom = "sttoken";
+        let pool = get_test_pool(pool_id, sttoken_denom, AssetOrdering::StTokenFirst);
+        deps.querier.mock_stableswap_pool(pool_id, &pool);
+        let add_msg = get_add_pool_msg(pool_id, pool);
+        execute(deps.as_mut(), env.clone(), info.clone(), add_msg).unwrap();
+
+        // Only the admin can add an oracle
+        let add_secondary_msg = ExecuteMsg::AddOracleSource {
+            contract_address: SECONDARY_ORACLE_ADDRESS.to_string(),
+            adapter: OracleAdapter::IcaOracle {},
+        };
+        let add_resp = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("non-admin", &[]),
+            add_secondary_msg.clone(),
+        );
+        assert_eq!(add_resp, Err(ContractError::Unauthorized {}));
+
+        // The config's oracle cannot be added as an additional oracle
+        let add_config_oracle_msg = ExecuteMsg::AddOracleSource {
+            contract_address: ORACLE_ADDRESS.to_string(),
+            adapter: OracleAdapter::IcaOracle {},
+        };
+        let add_resp = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            add_config_oracle_msg,
+        );
+        assert_eq!(
+            add_resp,
+            Err(ContractError::OracleSourceAlreadyExists {
+                contract_address: ORACLE_ADDRESS.to_string(),
+            })
+        );
+
+        // Add a second ICA Oracle and a pyth oracle
+        let add_resp = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            add_secondary_msg.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            add_resp.attributes,
+            vec![
+                attr("action", "add_oracle_source"),
+                attr("contract_address", SECONDARY_ORACLE_ADDRESS),
+                attr("adapter", "ica_oracle"),
+            ]
+        );
+
+        let pyth_adapter = OracleAdapter::Pyth {
+            price_feeds: vec![PythPriceFeedId {
+                denom: sttoken_denom.to_string(),
+                price_feed_id: "sttoken_feed".to_string(),
+            }],
+            max_confidence_bps: 50,
+        };
+        let add_pyth_msg = ExecuteMsg::AddOracleSource {
+            contract_address: PYTH_ORACLE_ADDRESS.to_string(),
+            adapter: pyth_adapter.clone(),
+        };
+        let add_resp = execute(deps.as_mut(), env.clone(), info.clone(), add_pyth_msg).unwrap();
+        assert_eq!(
+            add_resp.attributes,
+            vec![
+                attr("action", "add_oracle_source"),
+                attr("contract_address", PYTH_ORACLE_ADDRESS),
+                attr("adapter", "pyth"),
+                attr("price_feeds", "[sttoken: sttoken_feed]"),
+                attr("max_confidence_bps", "50"),
+            ]
+        );
+
+        // An oracle cannot be added twice
+        let add_resp = execute(deps.as_mut(), env.clone(), info.clone(), add_secondary_msg);
+        assert_eq!(
+            add_resp,
+            Err(ContractError::OracleSourceAlreadyExists {
+                contract_address: SECONDARY_ORACLE_ADDRESS.to_string(),
+            })
+        );
+
+        // Confirm both oracles are returned from the query
+        let query_resp = query(deps.as_ref(), env.clone(), QueryMsg::OracleSources {}).unwrap();
+        let oracle_sources: OracleSources = from_binary(&query_resp).unwrap();
+        assert_eq!(
+            oracle_sources.sources,
+            vec![
+                OracleSource {
+                    contract_address: Addr::unchecked(PYTH_ORACLE_ADDRESS),
+                    adapter: pyth_adapter,
+                },
+                OracleSource {
+                    contract_address: Addr::unchecked(SECONDARY_ORACLE_ADDRESS),
+                    adapter: OracleAdapter::IcaOracle {},
+                },
+            ]
+        );
+
+        // The quorum must be at least one
+        let update_config_msg = ExecuteMsg::UpdateConfig(UpdateConfigMsg {
+            oracle_quorum: Some(0),
+            ..Default::default()
+        });
+        let update_config_resp =
+            execute(deps.as_mut(), env.clone(), info.clone(), update_config_msg);
+        assert_eq!(
+            update_config_resp,
+            Err(ContractError::InvalidOracleQuorum {})
+        );
+
+        // Require at least two oracles to agree
+        let update_config_msg = ExecuteMsg::UpdateConfig(UpdateConfigMsg {
+            oracle_quorum: Some(2),
+            ..Default::default()
+        });
+        let update_config_resp =
+            execute(deps.as_mut(), env.clone(), info.clone(), update_config_msg).unwrap();
+        assert_eq!(
+            update_config_resp.attributes,
+            vec![
+                attr("action", "update_config"),
+                attr("previous_oracle_quorum", "1"),
+                attr("oracle_quorum", "2"),
+            ]
+        );
+
+        // With all three oracles available, the median redemption rate should be applied,
+        // and each oracle's redemption rate should be emitted
+        let block_time = 1_000_000;
+        deps.querier.mock_redemption_rate_for_oracle(
+            SECONDARY_ORACLE_ADDRESS,
+            sttoken_denom.to_string(),
+            Decimal::from_str("1.3").unwrap(),
+            block_time,
+        );
+        deps.querier
+            .mock_pyth_price("sttoken_feed", "125000000", "0", block_time as i64);
+        let update_resp = update_scaling_factor_at(
+            &mut deps,
+            &mut env,
+            pool_id,
+            sttoken_denom,
+            "1.2",
+            block_time,
+        )
+        .unwrap();
+        assert_eq!(
+            update_resp.attributes,
+            vec![
+                attr("action", "update_scaling_factor"),
+                attr("pool_id", "1"),
+                attr("redemption_rate", "1.25"),
+                attr(
+                    "oracle_redemption_rates",
+                    "sttoken: [oracle: 1.2, pyth_oracle: 1.25, secondary_oracle: 1.3]"
+                ),
+                attr("scaling_factors", "[100000, 125000]"),
+            ]
+        );
+
+        // Once the second ICA Oracle is stale, it should be discarded, and the median
+        // should be taken from the remaining two oracles
+        let block_time = block_time + MAX_ORACLE_STALENESS_SECONDS + 1;
+        deps.querier
+            .mock_pyth_price("sttoken_feed", "132000000", "0", block_time as i64);
+        let update_resp = update_scaling_factor_at(
+            &mut deps,
+            &mut env,
+            pool_id,
+            sttoken_denom,
+            "1.3",
+            block_time,
+        )
+        .unwrap();
+        assert_eq!(
+            update_resp.attributes,
+            vec![
+                attr("action", "update_scaling_factor"),
+                attr("pool_id", "1"),
+                attr("redemption_rate", "1.31"),
+                attr(
+                    "oracle_redemption_rates",
+                    "sttoken: [oracle: 1.3, pyth_oracle: 1.32, secondary_oracle: stale]"
+                ),
+                attr("scaling_factors", "[100000, 131000]"),
+            ]
+        );
+
+        // If the pyth price's confidence interval is too wide, it should be discarded
+        // and the quorum is no longer met
+        deps.querier
+            .mock_pyth_price("sttoken_feed", "132000000", "1000000", block_time as i64);
+        let update_resp = update_scaling_factor_at(
+            &mut deps,
+            &mut env,
+            pool_id,
+            sttoken_denom,
+            "1.3",
+            block_time + 50,
+        );
+        assert_eq!(
+            update_resp,
+            Err(ContractError::OracleQuorumNotMet {
+                token: sttoken_denom.to_string(),
+                responses: 1,
+                quorum: 2,
+            })
+        );
+
+        // If the pyth price is also invalid, the quorum is no longer met
+        deps.querier
+            .mock_pyth_price("sttoken_feed", "0", "0", block_time as i64);
+        let update_resp = update_scaling_factor_at(
+            &mut deps,
+            &mut env,
+            pool_id,
+            sttoken_denom,
+            "1.3",
+            block_time + 100,
+        );
+        assert_eq!(
+            update_resp,
+            Err(ContractError::OracleQuorumNotMet {
+                token: sttoken_denom.to_string(),
+                responses: 1,
+                quorum: 2,
+            })
+        );
+
+        // Only the admin can remove an oracle, and the oracle must be registered
+        let remove_pyth_msg = ExecuteMsg::RemoveOracleSource {
+            contract_address: PYTH_ORACLE_ADDRESS.to_string(),
+        };
+        let remove_resp = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("non-admin", &[]),
+            remove_pyth_msg.clone(),
+        );
+        assert_eq!(remove_resp, Err(ContractError::Unauthorized {}));
+
+        let remove_resp = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::RemoveOracleSource {
+                contract_address: "unknown_oracle".to_string(),
+            },
+        );
+        assert_eq!(
+            remove_resp,
+            Err(ContractError::OracleSourceNotFound {
+                contract_address: "unknown_oracle".to_string(),
+            })
+        );
+
+        // Remove both additional oracles and lower the quorum
+        let remove_resp =
+            execute(deps.as_mut(), env.clone(), info.clone(), remove_pyth_msg).unwrap();
+        assert_eq!(
+            remove_resp.attributes,
+            vec![
+                attr("action", "remove_oracle_source"),
+                attr("contract_address", PYTH_ORACLE_ADDRESS),
+            ]
+        );
+        let remove_secondary_msg = ExecuteMsg::RemoveOracleSource {
+            contract_address: SECONDARY_ORACLE_ADDRESS.to_string(),
+        };
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            remove_secondary_msg,
+        )
+        .unwrap();
+
+        let update_config_msg = ExecuteMsg::UpdateConfig(UpdateConfigMsg {
+            oracle_quorum: Some(1),
+            ..Default::default()
+        });
+        execute(deps.as_mut(), env.clone(), info.clone(), update_config_msg).unwrap();
+
+        let query_resp = query(deps.as_ref(), env.clone(), QueryMsg::OracleSources {}).unwrap();
+        let oracle_sources: OracleSources = from_binary(&query_resp).unwrap();
+        assert_eq!(oracle_sources.sources, vec![]);
+
+        // The config's oracle should now be queried on it's own
+        let update_resp = update_scaling_factor_at(
+            &mut deps,
+            &mut env,
+            pool_id,
+            sttoken_denom,
+            "1.32",
+            block_time + 200,
+        )
+        .unwrap();
+        assert_eq!(
+            update_resp.attributes,
+            vec![
+                attr("action", "update_scaling_factor"),
+                attr("pool_id", "1"),
+                attr("redemption_rate", "1.32"),
+                attr("scaling_factors", "[100000, 132000]"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unauthorized() {
+        let (mut deps, env, _) = default_instantiate();
+
+        // Create info with non-admin sender
+        let invalid_info: MessageInfo = mock_info("not_admin", &[]);
+
+        // Attempt to add the pool with a non-admin address
+        let pool_id = 1;
+        let pool = get_test_pool(pool_id, "stA", AssetOrdering::StTokenFirst);
+        let add_msg = get_add_pool_msg(pool_id, pool);
+        let add_resp = execute(deps.as_mut(), env.clone(), invalid_info.clone(), add_msg);
+
+        assert_eq!(add_resp, Err(ContractError::Unauthorized {}));
+
+        // Attempt to remove a pool with a non-admin address
+        let remove_msg = ExecuteMsg::RemovePool { pool_id: 1 };
+        let remove_resp = execute(deps.as_mut(), env.clone(), invalid_info.clone(), remove_msg);
+
+        assert_eq!(remove_resp, Err(ContractError::Unauthorized {}));
+
+        // Attempt to update a pool with a non-admin address
+        let update_pool_msg = ExecuteMsg::UpdatePool(UpdatePoolMsg {
+            pool_id: 1,
+            max_oracle_staleness_seconds: Some(1),
+            ..Default::default()
+        });
+        let update_pool_resp = execute(
+            deps.as_mut(),
+            env.clone(),
+            invalid_info.clone(),
+            update_pool_msg,
+        );
+
+        assert_eq!(update_pool_resp, Err(ContractError::Unauthorized {}));
+
+        // Attempt to acknowledge a redemption rate decrease with a non-admin address
+        let acknowledge_msg = ExecuteMsg::AcknowledgeRedemptionRateDecrease { pool_id: 1 };
+        let acknowledge_resp = execute(
+            deps.as_mut(),
+            env.clone(),
+            invalid_info.clone(),
+            acknowledge_msg,
+        );
+
+        assert_eq!(acknowledge_resp, Err(ContractError::Unauthorized {}));
+
+        // Attempt to propose a scaling factor override with a non-admin address
+        let propose_msg = ExecuteMsg::ProposeScalingFactorOverride {
+            pool_id: 1,
+            scaling_factors: vec![1, 1],
+            max_oracle_deviation_bps: None,
+        };
+        let propose_resp = execute(deps.as_mut(), env, invalid_info, propose_msg);
+
+        assert_eq!(propose_resp, Err(ContractError::Unauthorized {}));
+    }
+
+    #[test]
+    fn test_update_scaling_factor() {
+        let pool_id = 2;
+        let sttoken_denom = "stuosmo";
+        let asset_ordering = AssetOrdering::StTokenFirst;
+        let pool = get_test_pool(pool_id, sttoken_denom, asset_ordering);
+
+        let block_time = 1_000_000;
+        let redemption_rate = Decimal::from_str("1.2").unwrap();
+        let expected_scaling_factors = vec![100000, 120000];
+
+        // Mock out the block time and the oracle query response
+        let (mut deps, mut env, info) = default_instantiate();
+        env.block.time = Timestamp::from_seconds(block_time);
+        deps.querier.mock_oracle_redemption_rate(
+            sttoken_denom.to_string(),
+            redemption_rate,
+            block_time - 60,
+        );
+
+        // Mock out the stableswap pool on Osmosis
+        deps.querier.mock_stableswap_pool(pool_id, &pool);
+
+        // Add a pool
+        let add_pool_msg = get_add_pool_msg(pool_id, pool);
+        execute(deps.as_mut(), env.clone(), info.clone(), add_pool_msg).unwrap();
+
+        // Update the scaling factor
+        let update_msg = ExecuteMsg::UpdateScalingFactor { pool_id: 2 };
+        let update_pool_resp =
+            execute(deps.as_mut(), env.clone(), info.clone(), update_msg).unwrap();
+
+        // Confrim attributes
+        assert_eq!(
+            update_pool_resp.attributes,
+            vec![
+                attr("action", "update_scaling_factor"),
+                attr("pool_id", "2"),
+                attr("redemption_rate", "1.2"),
+                attr("scaling_factors", "[100000, 120000]")
+            ]
+        );
+
+        // Confirm pool was updated with the current block time
+        let query_pool_msg = QueryMsg::Pool { pool_id: 2 };
+        let query_pool_resp = query(deps.as_ref(), env.clone(), query_pool_msg).unwrap();
+
+        let queried_pool: Pool = from_binary(&query_pool_resp).unwrap();
+        let queried_pool_cloned = queried_pool.clone();
+        let expected_pool = Pool {
+            last_updated: block_time,
+            last_scaling_factors: expected_scaling_factors.clone(),
+            last_redemption_rate: Some(redemption_rate),
+            last_oracle_update_time: Some(block_time - 60),
+            ..queried_pool_cloned
+        };
+
+        assert_eq!(queried_pool, expected_pool);
+
+        // Confirm the osmosis tx was appended to the message response
+        let expected_update_msg: CosmosMsg = MsgStableSwapAdjustScalingFactors {
+            sender: env.contract.address.to_string(),
+            pool_id: 2,
+            scaling_factors: expected_scaling_factors,
+        }
+        .into();
+
+        assert_eq!(update_pool_resp.messages.len(), 1);
+        assert_eq!(update_pool_resp.messages[0].msg, expected_update_msg);
+
+        // Update again at a later time before the oracle has been updated, the update
+        // should be skipped
+        env.block.time = Timestamp::from_seconds(block_time + 100);
+        let update_msg = ExecuteMsg::UpdateScalingFactor { pool_id: 2 };
+        let update_pool_resp =
+            execute(deps.as_mut(), env.clone(), info.clone(), update_msg).unwrap();
+        assert_eq!(
+            update_pool_resp.attributes,
+            vec![
+                attr("action", "update_scaling_factor"),
+                attr("pool_id", "2"),
+                attr("redemption_rate", "1.2"),
+                attr("reason", "oracle_not_updated"),
+            ]
+        );
+        assert_eq!(update_pool_resp.messages.len(), 0);
+
+        // Once the oracle is updated with the same redemption rate, since the scaling
+        // factors have not changed, the update should still be skipped
+        deps.querier.mock_oracle_redemption_rate(
+            sttoken_denom.to_string(),
+            redemption_rate,
+            block_time + 50,
+        );
+        let update_msg = ExecuteMsg::UpdateScalingFactor { pool_id: 2 };
+        let update_pool_resp =
+            execute(deps.as_mut(), env.clone(), info.clone(), update_msg).unwrap();
+        assert_eq!(
+            update_pool_resp.attributes,
+            vec![
+                attr("action", "update_scaling_factor"),
+                attr("pool_id", "2"),
+                attr("redemption_rate", "1.2"),
+                attr("reason", "scaling_factors_unchanged"),
+            ]
+        );
+        assert_eq!(update_pool_resp.messages.len(), 0);
+
+        // Confirm the pool was not modified
+        let query_pool_msg = QueryMsg::Pool { pool_id: 2 };
+        let query_pool_resp = query(deps.as_ref(), env.clone(), query_pool_msg).unwrap();
+        let skipped_pool: Pool = from_binary(&query_pool_resp).unwrap();
+        assert_eq!(skipped_pool, queried_pool);
+
+        // Attempt to update a non-existent pool, it should error
+        let update_msg = ExecuteMsg::UpdateScalingFactor { pool_id: 1 };
+        let update_pool_resp = execute(deps.as_mut(), env.clone(), info, update_msg);
+        assert_eq!(
+            update_pool_resp,
+            Err(ContractError::PoolNotFound { pool_id: 1 })
+        );
+    }
+
+    #[test]
+    fn test_update_all_scaling_factors() {
+        let (mut deps, mut env, info) = default_instantiate();
+        let block_time = 1_000_000;
+        env.block.time = Timestamp::from_seconds(block_time);
+
+        // Register pools 1 and 3 with stuosmo, pool 2 with stujuno, and pool 4 with a
+        // denom that's missing from the oracle
+        let pools = vec![
+            get_test_pool(1, "stuosmo", AssetOrdering::StTokenFirst),
+            get_test_pool(2, "stujuno", AssetOrdering::StTokenFirst),
+            get_test_pool(3, "stuosmo", AssetOrdering::NativeTokenFirst),
+            get_test_pool(4, "stuatom", AssetOrdering::StTokenFirst),
+        ];
+        for pool in pools {
+            deps.querier.mock_stableswap_pool(pool.pool_id, &pool);
+            let add_pool_msg = get_add_pool_msg(pool.pool_id, pool);
+            execute(deps.as_mut(), env.clone(), info.clone(), add_pool_msg).unwrap();
+        }
+
+        deps.querier.mock_oracle_redemption_rate(
+            "stuosmo".to_string(),
+            Decimal::from_str("1.2").unwrap(),
+            block_time - 300,
+        );
+        deps.querier.mock_oracle_redemption_rate(
+            "stujuno".to_string(),
+            Decimal::from_str("1.5").unwrap(),
+            block_time - 300,
+        );
+
+        // Update all pools - pool 4 should be skipped without failing the batch
+        let update_msg = ExecuteMsg::UpdateAllScalingFactors {
+            pool_ids: None,
+            start_after: None,
+            limit: None,
+        };
+        let update_resp = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("keeper", &[]),
+            update_msg,
+        )
+        .unwrap();
+
+        assert_eq!(
+            update_resp.attributes,
+            vec![
+                attr("action", "update_all_scaling_factors"),
+                attr("updated_pools", "[1, 2, 3]"),
+                attr("skipped_pools", "[4]"),
+            ]
+        );
+        assert_eq!(
+            update_resp.events,
+            vec![
+                Event::new("update_scaling_factor")
+                    .add_attribute("pool_id", "1")
+                    .add_attribute("status", "updated")
+                    .add_attribute("redemption_rate", "1.2")
+                    .add_attribute("scaling_factors", "[100000, 120000]"),
+                Event::new("update_scaling_factor")
+                    .add_attribute("pool_id", "2")
+                    .add_attribute("status", "updated")
+                    .add_attribute("redemption_rate", "1.5")
+                    .add_attribute("scaling_factors", "[100000, 150000]"),
+                Event::new("update_scaling_factor")
+                    .add_attribute("pool_id", "3")
+                    .add_attribute("status", "updated")
+                    .add_attribute("redemption_rate", "1.2")
+                    .add_attribute("scaling_factors", "[120000, 100000]"),
+                Event::new("update_scaling_factor")
+                    .add_attribute("pool_id", "4")
+                    .add_attribute("status", "skipped")
+                    .add_attribute(
+                        "reason",
+                        ContractError::UnableToQueryRedemptionRate {
+                            token: "stuatom".to_string(),
+                            error: "Generic error: Querier system error: Unknown system error"
+                                .to_string(),
+                        }
+                        .to_string()
+                    ),
+            ]
+        );
+
+        // Confirm an adjust message was submitted for each updated pool
+        let expected_msgs: Vec<CosmosMsg> = vec![
+            (1, vec![100000, 120000]),
+            (2, vec![100000, 150000]),
+            (3, vec![120000, 100000]),
+        ]
+        .into_iter()
+        .map(|(pool_id, scaling_factors)| {
+            MsgStableSwapAdjustScalingFactors {
+                sender: env.contract.address.to_string(),
+                pool_id,
+                scaling_factors,
+            }
+            .into()
+        })
+        .collect();
+        let msgs: Vec<CosmosMsg> = update_resp.messages.into_iter().map(|m| m.msg).collect();
+        assert_eq!(msgs, expected_msgs);
 
-        let mut deps = OwnedDeps {
-            storage: MockStorage::default(),
-            api: MockApi::default(),
-            querier: custom_querier,
-            custom_query_type: Default::default(),
+        // Pause pool 1 and update an explicit list of pools, including one that's not registered
+        deps.querier.mock_oracle_redemption_rate(
+            "stujuno".to_string(),
+            Decimal::from_str("1.6").unwrap(),
+            block_time - 200,
+        );
+        let pause_msg = ExecuteMsg::Pause { pool_id: Some(1) };
+        execute(deps.as_mut(), env.clone(), info.clone(), pause_msg).unwrap();
+
+        let update_msg = ExecuteMsg::UpdateAllScalingFactors {
+            pool_ids: Some(vec![5, 2, 1, 2]),
+            start_after: None,
+            limit: None,
+        };
+        let update_resp = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("keeper", &[]),
+            update_msg,
+        )
+        .unwrap();
+
+        assert_eq!(
+            update_resp.attributes,
+            vec![
+                attr("action", "update_all_scaling_factors"),
+                attr("updated_pools", "[2]"),
+                attr("skipped_pools", "[1, 5]"),
+            ]
+        );
+        assert_eq!(
+            update_resp.events[0],
+            Event::new("update_scaling_factor")
+                .add_attribute("pool_id", "1")
+                .add_attribute("status", "skipped")
+                .add_attribute("reason", ContractError::Paused {}.to_string())
+        );
+        assert_eq!(
+            update_resp.events[2],
+            Event::new("update_scaling_factor")
+                .add_attribute("pool_id", "5")
+                .add_attribute("status", "skipped")
+                .add_attribute(
+                    "reason",
+                    ContractError::PoolNotFound { pool_id: 5 }.to_string()
+                )
+        );
+        assert_eq!(update_resp.messages.len(), 1);
+
+        // Paginate through the registered pools
+        deps.querier.mock_oracle_redemption_rate(
+            "stuosmo".to_string(),
+            Decimal::from_str("1.3").unwrap(),
+            block_time - 100,
+        );
+        deps.querier.mock_oracle_redemption_rate(
+            "stujuno".to_string(),
+            Decimal::from_str("1.7").unwrap(),
+            block_time - 100,
+        );
+        let update_msg = ExecuteMsg::UpdateAllScalingFactors {
+            pool_ids: None,
+            start_after: Some(1),
+            limit: Some(2),
+        };
+        let update_resp = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("keeper", &[]),
+            update_msg,
+        )
+        .unwrap();
+        assert_eq!(
+            update_resp.attributes,
+            vec![
+                attr("action", "update_all_scaling_factors"),
+                attr("updated_pools", "[2, 3]"),
+                attr("skipped_pools", "[]"),
+            ]
+        );
+
+        // Transfer control of pool 3 to a different address on Osmosis, it should be
+        // skipped without failing the batch or modifying the pool
+        deps.querier.mock_invalid_stableswap_pool(
+            3,
+            StableswapPool {
+                id: 3,
+                scaling_factor_controller: "other_controller".to_string(),
+                ..Default::default()
+            },
+        );
+        let block_time = block_time + 100;
+        env.block.time = Timestamp::from_seconds(block_time);
+        deps.querier.mock_oracle_redemption_rate(
+            "stuosmo".to_string(),
+            Decimal::from_str("1.4").unwrap(),
+            block_time,
+        );
+        deps.querier.mock_oracle_redemption_rate(
+            "stujuno".to_string(),
+            Decimal::from_str("1.8").unwrap(),
+            block_time,
+        );
+        let update_msg = ExecuteMsg::UpdateAllScalingFactors {
+            pool_ids: Some(vec![2, 3]),
+            start_after: None,
+            limit: None,
+        };
+        let update_resp = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("keeper", &[]),
+            update_msg,
+        )
+        .unwrap();
+        assert_eq!(
+            update_resp.attributes,
+            vec![
+                attr("action", "update_all_scaling_factors"),
+                attr("updated_pools", "[2]"),
+                attr("skipped_pools", "[3]"),
+            ]
+        );
+        assert_eq!(
+            update_resp.events[1],
+            Event::new("update_scaling_factor")
+                .add_attribute("pool_id", "3")
+                .add_attribute("status", "skipped")
+                .add_attribute(
+                    "reason",
+                    ContractError::NotScalingFactorController {
+                        pool_id: 3,
+                        scaling_factor_controller: "other_controller".to_string(),
+                    }
+                    .to_string()
+                )
+        );
+        assert_eq!(update_resp.messages.len(), 1);
+
+        let query_resp = query(deps.as_ref(), env.clone(), QueryMsg::Pool { pool_id: 3 }).unwrap();
+        let pool: Pool = from_binary(&query_resp).unwrap();
+        assert_eq!(pool.last_scaling_factors, vec![130000, 100000]);
+
+        // Once paused globally, the batch should fail
+        let pause_msg = ExecuteMsg::Pause { pool_id: None };
+        execute(deps.as_mut(), env.clone(), info, pause_msg).unwrap();
+
+        let update_msg = ExecuteMsg::UpdateAllScalingFactors {
+            pool_ids: None,
+            start_after: None,
+            limit: None,
+        };
+        let update_resp = execute(deps.as_mut(), env, mock_info("keeper", &[]), update_msg);
+        assert_eq!(update_resp, Err(ContractError::Paused {}));
+    }
+
+    #[test]
+    fn test_update_pool() {
+        let (mut deps, env, info) = default_instantiate();
+
+        // Add a pool without a staleness override
+        let pool_id = 1;
+        let pool = get_test_pool(pool_id, "stA", AssetOrdering::StTokenFirst);
+        deps.querier.mock_stableswap_pool(pool_id, &pool);
+        let add_msg = get_add_pool_msg(pool_id, pool.clone());
+        execute(deps.as_mut(), env.clone(), info.clone(), add_msg).unwrap();
+
+        // Override the staleness for the pool
+        let update_msg = ExecuteMsg::UpdatePool(UpdatePoolMsg {
+            pool_id,
+            max_oracle_staleness_seconds: Some(600),
+            ..Default::default()
+        });
+        let update_resp = execute(deps.as_mut(), env.clone(), info.clone(), update_msg).unwrap();
+        assert_eq!(
+            update_resp.attributes,
+            vec![
+                attr("action", "update_pool"),
+                attr("pool_id", "1"),
+                attr("max_oracle_staleness_seconds", "600"),
+            ]
+        );
+
+        // Confirm the pool was updated
+        let query_pool_msg = QueryMsg::Pool { pool_id };
+        let query_resp = query(deps.as_ref(), env.clone(), query_pool_msg).unwrap();
+        let pool_resp: Pool = from_binary(&query_resp).unwrap();
+        assert_eq!(
+            pool_resp,
+            Pool {
+                max_oracle_staleness_seconds: Some(600),
+                ..pool.clone()
+            }
+        );
+
+        // Attempt to both set and clear the staleness override, it should fail
+        let update_msg = ExecuteMsg::UpdatePool(UpdatePoolMsg {
+            pool_id,
+            max_oracle_staleness_seconds: Some(900),
+            clear_settings: Some(vec![PoolSetting::MaxOracleStalenessSeconds]),
+            ..Default::default()
+        });
+        let update_resp = execute(deps.as_mut(), env.clone(), info.clone(), update_msg);
+        assert_eq!(
+            update_resp,
+            Err(ContractError::ConflictingPoolSetting {
+                setting: "max_oracle_staleness_seconds".to_string()
+            })
+        );
+
+        // Clear the staleness override so the pool falls back to the config default
+        let update_msg = ExecuteMsg::UpdatePool(UpdatePoolMsg {
+            pool_id,
+            clear_settings: Some(vec![
+                PoolSetting::MaxOracleStalenessSeconds,
+                PoolSetting::TwapWindowSeconds,
+            ]),
+            ..Default::default()
+        });
+        let update_resp = execute(deps.as_mut(), env.clone(), info.clone(), update_msg).unwrap();
+        assert_eq!(
+            update_resp.attributes,
+            vec![
+                attr("action", "update_pool"),
+                attr("pool_id", "1"),
+                attr(
+                    "cleared_settings",
+                    "[max_oracle_staleness_seconds, twap_window_seconds]"
+                ),
+            ]
+        );
+
+        let query_pool_msg = QueryMsg::Pool { pool_id };
+        let query_resp = query(deps.as_ref(), env.clone(), query_pool_msg).unwrap();
+        let pool_resp: Pool = from_binary(&query_resp).unwrap();
+        assert_eq!(
+            pool_resp,
+            Pool {
+                max_oracle_staleness_seconds: None,
+                twap_window_seconds: None,
+                ..pool
+            }
+        );
+
+        // Attempt to update a pool that was never registered, it should fail
+        let update_msg = ExecuteMsg::UpdatePool(UpdatePoolMsg {
+            pool_id: 2,
+            max_oracle_staleness_seconds: Some(600),
+            ..Default::default()
+        });
+        let update_resp = execute(deps.as_mut(), env, info, update_msg);
+        assert_eq!(update_resp, Err(ContractError::PoolNotFound { pool_id: 2 }));
+    }
+
+    #[test]
+    fn test_update_scaling_factor_stale_redemption_rate() {
+        let pool_id = 1;
+        let sttoken_denom = "stuosmo";
+        let pool = get_test_pool(pool_id, sttoken_denom, AssetOrdering::StTokenFirst);
+
+        let block_time = 1_000_000;
+        let redemption_rate = Decimal::from_str("1.2").unwrap();
+
+        let (mut deps, mut env, info) = default_instantiate();
+        env.block.time = Timestamp::from_seconds(block_time);
+        deps.querier.mock_stableswap_pool(pool_id, &pool);
+
+        let add_pool_msg = get_add_pool_msg(pool_id, pool);
+        execute(deps.as_mut(), env.clone(), info.clone(), add_pool_msg).unwrap();
+
+        let update_msg = ExecuteMsg::UpdateScalingFactor { pool_id };
+
+        // Fresh redemption rate - the update should succeed
+        deps.querier.mock_oracle_redemption_rate(
+            sttoken_denom.to_string(),
+            redemption_rate,
+            block_time - 1,
+        );
+        execute(deps.as_mut(), env.clone(), info.clone(), update_msg.clone()).unwrap();
+
+        // Redemption rate exactly at the staleness threshold - the update should succeed
+        deps.querier.mock_oracle_redemption_rate(
+            sttoken_denom.to_string(),
+            redemption_rate,
+            block_time - MAX_ORACLE_STALENESS_SECONDS,
+        );
+        execute(deps.as_mut(), env.clone(), info.clone(), update_msg.clone()).unwrap();
+
+        // Redemption rate one second past the threshold - the update should fail
+        deps.querier.mock_oracle_redemption_rate(
+            sttoken_denom.to_string(),
+            redemption_rate,
+            block_time - MAX_ORACLE_STALENESS_SECONDS - 1,
+        );
+        let resp = execute(deps.as_mut(), env.clone(), info.clone(), update_msg.clone());
+        assert_eq!(
+            resp,
+            Err(ContractError::StaleRedemptionRate {
+                token: sttoken_denom.to_string(),
+                age: MAX_ORACLE_STALENESS_SECONDS + 1,
+                max_staleness: MAX_ORACLE_STALENESS_SECONDS,
+            })
+        );
+
+        // Tighten the staleness for the pool, and confirm a previously fresh rate is now rejected
+        let update_pool_msg = ExecuteMsg::UpdatePool(UpdatePoolMsg {
+            pool_id,
+            max_oracle_staleness_seconds: Some(60),
+            ..Default::default()
+        });
+        execute(deps.as_mut(), env.clone(), info.clone(), update_pool_msg).unwrap();
+
+        deps.querier.mock_oracle_redemption_rate(
+            sttoken_denom.to_string(),
+            redemption_rate,
+            block_time - 61,
+        );
+        let resp = execute(deps.as_mut(), env, info, update_msg);
+        assert_eq!(
+            resp,
+            Err(ContractError::StaleRedemptionRate {
+                token: sttoken_denom.to_string(),
+                age: 61,
+                max_staleness: 60,
+            })
+        );
+    }
+
+    #[test]
+    fn test_update_scaling_factor_min_update_interval() {
+        let pool_id = 1;
+        let sttoken_denom = "stuosmo";
+        let pool = get_test_pool(pool_id, sttoken_denom, AssetOrdering::StTokenFirst);
+
+        let (mut deps, mut env, info) = default_instantiate();
+        deps.querier.mock_stableswap_pool(pool_id, &pool);
+
+        let add_pool_msg = get_add_pool_msg(pool_id, pool);
+        execute(deps.as_mut(), env.clone(), info.clone(), add_pool_msg).unwrap();
+
+        // Set a default minimum interval of 1 hour in the config
+        let update_config_msg = ExecuteMsg::UpdateConfig(UpdateConfigMsg {
+            min_update_interval_seconds: Some(3_600),
+            ..Default::default()
+        });
+        execute(deps.as_mut(), env.clone(), info.clone(), update_config_msg).unwrap();
+
+        // The first update should go through
+        update_scaling_factor_at(&mut deps, &mut env, pool_id, sttoken_denom, "1.2", 10_000)
+            .unwrap();
+
+        let query_msg = QueryMsg::NextUpdateTime { pool_id };
+        let query_resp = query(deps.as_ref(), env.clone(), query_msg.clone()).unwrap();
+        let next_update_time: u64 = from_binary(&query_resp).unwrap();
+        assert_eq!(next_update_time, 13_600);
+
+        // Updating again before the interval has passed should fail
+        let resp =
+            update_scaling_factor_at(&mut deps, &mut env, pool_id, sttoken_denom, "1.3", 13_599);
+        assert_eq!(
+            resp,
+            Err(ContractError::UpdateTooFrequent {
+                pool_id,
+                next_update_time: 13_600,
+            })
+        );
+
+        // Once the interval has passed, the update should succeed
+        update_scaling_factor_at(&mut deps, &mut env, pool_id, sttoken_denom, "1.3", 13_600)
+            .unwrap();
+
+        // Override the interval for the pool with a shorter interval
+        let update_pool_msg = ExecuteMsg::UpdatePool(UpdatePoolMsg {
+            pool_id,
+            min_update_interval_seconds: Some(60),
+            ..Default::default()
+        });
+        let resp = execute(deps.as_mut(), env.clone(), info, update_pool_msg).unwrap();
+        assert_eq!(
+            resp.attributes,
+            vec![
+                attr("action", "update_pool"),
+                attr("pool_id", "1"),
+                attr("min_update_interval_seconds", "60"),
+            ]
+        );
+
+        let query_resp = query(deps.as_ref(), env.clone(), query_msg).unwrap();
+        let next_update_time: u64 = from_binary(&query_resp).unwrap();
+        assert_eq!(next_update_time, 13_660);
+
+        update_scaling_factor_at(&mut deps, &mut env, pool_id, sttoken_denom, "1.4", 13_660)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_update_scaling_factor_circuit_breaker() {
+        let pool_id = 1;
+        let sttoken_denom = "stuosmo";
+        let pool = Pool {
+            circuit_breaker: Some(CircuitBreaker {
+                max_change_bps: 1000,
+                action: CircuitBreakerAction::Reject,
+            }),
+            ..get_test_pool(pool_id, sttoken_denom, AssetOrdering::StTokenFirst)
+        };
+
+        let block_time = 1_000_000;
+        let (mut deps, mut env, info) = default_instantiate();
+        env.block.time = Timestamp::from_seconds(block_time);
+        deps.querier.mock_stableswap_pool(pool_id, &pool);
+
+        let add_pool_msg = get_add_pool_msg(pool_id, pool);
+        execute(deps.as_mut(), env.clone(), info.clone(), add_pool_msg).unwrap();
+
+        // The first update has nothing to compare against, so it should go through
+        let update_msg = ExecuteMsg::UpdateScalingFactor { pool_id };
+        deps.querier.mock_oracle_redemption_rate(
+            sttoken_denom.to_string(),
+            Decimal::from_str("1.2").unwrap(),
+            block_time - 20,
+        );
+        let resp = execute(deps.as_mut(), env.clone(), info.clone(), update_msg.clone()).unwrap();
+        assert_eq!(resp.messages.len(), 1);
+        assert_eq!(resp.events.len(), 0);
+
+        // Update the redemption rate to a value that's 25% higher, it should be rejected
+        deps.querier.mock_oracle_redemption_rate(
+            sttoken_denom.to_string(),
+            Decimal::from_str("1.5").unwrap(),
+            block_time - 10,
+        );
+        let resp = execute(deps.as_mut(), env.clone(), info.clone(), update_msg.clone()).unwrap();
+        assert_eq!(resp.messages.len(), 0);
+        assert_eq!(
+            resp.attributes,
+            vec![
+                attr("action", "update_scaling_factor"),
+                attr("pool_id", "1"),
+                attr("redemption_rate", "1.5"),
+                attr("circuit_breaker", "rejected"),
+            ]
+        );
+        assert_eq!(
+            resp.events,
+            vec![
+                Event::new("scaling_factor_circuit_breaker").add_attributes(vec![
+                    attr("pool_id", "1"),
+                    attr("circuit_breaker_action", "reject"),
+                    attr("max_change_bps", "1000"),
+                    attr("previous_scaling_factors", "[100000, 120000]"),
+                    attr("proposed_scaling_factors", "[100000, 150000]"),
+                ])
+            ]
+        );
+
+        // Confirm the last applied scaling factors were not modified, and the rejected
+        // redemption rate was not recorded in the history
+        let query_resp = query(deps.as_ref(), env.clone(), QueryMsg::Pool { pool_id }).unwrap();
+        let queried_pool: Pool = from_binary(&query_resp).unwrap();
+        assert_eq!(queried_pool.last_scaling_factors, vec![100000, 120000]);
+
+        let history_msg = QueryMsg::RedemptionRateHistory {
+            denom: sttoken_denom.to_string(),
+            window_seconds: None,
+        };
+        let query_resp = query(deps.as_ref(), env.clone(), history_msg.clone()).unwrap();
+        let history: RedemptionRateHistory = from_binary(&query_resp).unwrap();
+        assert_eq!(
+            history.samples,
+            vec![RedemptionRateSample {
+                update_time: block_time - 20,
+                redemption_rate: Decimal::from_str("1.2").unwrap(),
+            }]
+        );
+
+        // Switch the circuit breaker to clamp mode
+        let update_pool_msg = ExecuteMsg::UpdatePool(UpdatePoolMsg {
+            pool_id,
+            circuit_breaker: Some(CircuitBreaker {
+                max_change_bps: 1000,
+                action: CircuitBreakerAction::Clamp,
+            }),
+            ..Default::default()
+        });
+        execute(deps.as_mut(), env.clone(), info.clone(), update_pool_msg).unwrap();
+
+        // Update again, this time the scaling factor should be clamped to a 10% increase
+        let resp = execute(deps.as_mut(), env.clone(), info, update_msg).unwrap();
+        assert_eq!(
+            resp.attributes,
+            vec![
+                attr("action", "update_scaling_factor"),
+                attr("pool_id", "1"),
+                attr("redemption_rate", "1.5"),
+                attr("circuit_breaker", "clamped"),
+                attr("scaling_factors", "[100000, 132000]"),
+            ]
+        );
+        assert_eq!(resp.events.len(), 1);
+
+        let expected_adjust_msg: CosmosMsg = MsgStableSwapAdjustScalingFactors {
+            sender: env.contract.address.to_string(),
+            pool_id,
+            scaling_factors: vec![100000, 132000],
+        }
+        .into();
+        assert_eq!(resp.messages.len(), 1);
+        assert_eq!(resp.messages[0].msg, expected_adjust_msg);
+
+        let query_resp = query(deps.as_ref(), env.clone(), QueryMsg::Pool { pool_id }).unwrap();
+        let queried_pool: Pool = from_binary(&query_resp).unwrap();
+        assert_eq!(queried_pool.last_scaling_factors, vec![100000, 132000]);
+
+        // The clamped redemption rate should be recorded in the history
+        let query_resp = query(deps.as_ref(), env, history_msg).unwrap();
+        let history: RedemptionRateHistory = from_binary(&query_resp).unwrap();
+        assert_eq!(
+            history.samples,
+            vec![
+                RedemptionRateSample {
+                    update_time: block_time - 20,
+                    redemption_rate: Decimal::from_str("1.2").unwrap(),
+                },
+                RedemptionRateSample {
+                    update_time: block_time - 10,
+                    redemption_rate: Decimal::from_str("1.5").unwrap(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_update_scaling_factor_redemption_rate_decrease() {
+        let pool_id = 1;
+        let sttoken_denom = "stuosmo";
+        let pool = Pool {
+            max_redemption_rate_decrease_bps: Some(100),
+            ..get_test_pool(pool_id, sttoken_denom, AssetOrdering::StTokenFirst)
+        };
+
+        let (mut deps, mut env, info) = default_instantiate();
+        deps.querier.mock_stableswap_pool(pool_id, &pool);
+
+        let add_pool_msg = get_add_pool_msg(pool_id, pool);
+        execute(deps.as_mut(), env.clone(), info.clone(), add_pool_msg).unwrap();
+
+        // Set the initial redemption rate and then increase it
+        update_scaling_factor_at(&mut deps, &mut env, pool_id, sttoken_denom, "1.2", 1_000)
+            .unwrap();
+        let resp =
+            update_scaling_factor_at(&mut deps, &mut env, pool_id, sttoken_denom, "1.25", 2_000)
+                .unwrap();
+        assert_eq!(resp.events.len(), 0);
+
+        // Decrease within the 1% tolerance, it should be accepted and recorded
+        let resp =
+            update_scaling_factor_at(&mut deps, &mut env, pool_id, sttoken_denom, "1.24", 3_000)
+                .unwrap();
+        assert_eq!(
+            resp.events,
+            vec![Event::new("redemption_rate_decrease").add_attributes(vec![
+                attr("pool_id", "1"),
+                attr("previous_redemption_rate", "1.25"),
+                attr("redemption_rate", "1.24"),
+                attr("acknowledged", "false"),
+            ])]
+        );
+
+        // Decrease beyond the tolerance, it should be rejected
+        let resp =
+            update_scaling_factor_at(&mut deps, &mut env, pool_id, sttoken_denom, "1.1", 4_000);
+        assert_eq!(
+            resp,
+            Err(ContractError::RedemptionRateDecreaseExceedsTolerance {
+                pool_id,
+                previous_redemption_rate: Decimal::from_str("1.24").unwrap(),
+                redemption_rate: Decimal::from_str("1.1").unwrap(),
+            })
+        );
+
+        // The rejected redemption rate should not have been recorded in the history
+        let query_msg = QueryMsg::RedemptionRateHistory {
+            denom: sttoken_denom.to_string(),
+            window_seconds: None,
         };
+        let query_resp = query(deps.as_ref(), env.clone(), query_msg).unwrap();
+        let history: RedemptionRateHistory = from_binary(&query_resp).unwrap();
+        assert_eq!(
+            history.samples.last(),
+            Some(&RedemptionRateSample {
+                update_time: 3_000,
+                redemption_rate: Decimal::from_str("1.24").unwrap(),
+            })
+        );
+
+        // Acknowledge the decrease as the admin
+        let acknowledge_msg = ExecuteMsg::AcknowledgeRedemptionRateDecrease { pool_id };
+        let resp = execute(deps.as_mut(), env.clone(), info.clone(), acknowledge_msg).unwrap();
+        assert_eq!(
+            resp.attributes,
+            vec![
+                attr("action", "acknowledge_redemption_rate_decrease"),
+                attr("pool_id", "1"),
+            ]
+        );
+
+        // The decrease should now be accepted
+        let resp =
+            update_scaling_factor_at(&mut deps, &mut env, pool_id, sttoken_denom, "1.1", 5_000)
+                .unwrap();
+        assert_eq!(resp.messages.len(), 1);
+        assert_eq!(
+            resp.events,
+            vec![Event::new("redemption_rate_decrease").add_attributes(vec![
+                attr("pool_id", "1"),
+                attr("previous_redemption_rate", "1.24"),
+                attr("redemption_rate", "1.1"),
+                attr("acknowledged", "true"),
+            ])]
+        );
+
+        // The acknowledgement should have been consumed, so another large decrease is rejected
+        let resp =
+            update_scaling_factor_at(&mut deps, &mut env, pool_id, sttoken_denom, "1.0", 6_000);
+        assert_eq!(
+            resp,
+            Err(ContractError::RedemptionRateDecreaseExceedsTolerance {
+                pool_id,
+                previous_redemption_rate: Decimal::from_str("1.1").unwrap(),
+                redemption_rate: Decimal::from_str("1.0").unwrap(),
+            })
+        );
 
-        let msg = InstantiateMsg {
-            admin_address: ADMIN_ADDRESS.to_string(),
-            oracle_contract_address: ORACLE_ADDRESS.to_string(),
-        };
+        // Apply two small decreases within the same block, both should be recorded
+        deps.querier.mock_oracle_redemption_rate(
+            sttoken_denom.to_string(),
+            Decimal::from_str("1.095").unwrap(),
+            6_990,
+        );
+        env.block.time = Timestamp::from_seconds(7_000);
+        let update_msg = ExecuteMsg::UpdateScalingFactor { pool_id };
+        execute(deps.as_mut(), env.clone(), info.clone(), update_msg).unwrap();
+        update_scaling_factor_at(&mut deps, &mut env, pool_id, sttoken_denom, "1.09", 7_000)
+            .unwrap();
 
-        let resp = instantiate(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        // Confirm each accepted decrease was recorded
+        let query_msg = QueryMsg::RedemptionRateDecreases { pool_id };
+        let query_resp = query(deps.as_ref(), mock_env(), query_msg).unwrap();
+        let decreases: RedemptionRateDecreases = from_binary(&query_resp).unwrap();
+        assert_eq!(
+            decreases,
+            RedemptionRateDecreases {
+                decreases: vec![
+                    RedemptionRateDecrease {
+                        pool_id,
+                        previous_redemption_rate: Decimal::from_str("1.25").unwrap(),
+                        redemption_rate: Decimal::from_str("1.24").unwrap(),
+                        time: 3_000,
+                        acknowledged: false,
+                    },
+                    RedemptionRateDecrease {
+                        pool_id,
+                        previous_redemption_rate: Decimal::from_str("1.24").unwrap(),
+                        redemption_rate: Decimal::from_str("1.1").unwrap(),
+                        time: 5_000,
+                        acknowledged: true,
+                    },
+                    RedemptionRateDecrease {
+                        pool_id,
+                        previous_redemption_rate: Decimal::from_str("1.1").unwrap(),
+                        redemption_rate: Decimal::from_str("1.095").unwrap(),
+                        time: 7_000,
+                        acknowledged: false,
+                    },
+                    RedemptionRateDecrease {
+                        pool_id,
+                        previous_redemption_rate: Decimal::from_str("1.095").unwrap(),
+                        redemption_rate: Decimal::from_str("1.09").unwrap(),
+                        time: 7_000,
+                        acknowledged: false,
+                    },
+                ]
+            }
+        );
+
+        // Clear the pool's tolerance so that it falls back to the config's default, which
+        // rejects any decrease
+        let update_config_msg = ExecuteMsg::UpdateConfig(UpdateConfigMsg {
+            max_redemption_rate_decrease_bps: Some(0),
+            ..Default::default()
+        });
+        let resp = execute(deps.as_mut(), env.clone(), info.clone(), update_config_msg).unwrap();
         assert_eq!(
             resp.attributes,
             vec![
-                attr("action", "instantiate"),
-                attr("admin_address", ADMIN_ADDRESS.to_string()),
-                attr("oracle_contract_address", ORACLE_ADDRESS.to_string()),
+                attr("action", "update_config"),
+                attr(
+                    "previous_max_redemption_rate_decrease_bps",
+                    MAX_REDEMPTION_RATE_DECREASE_BPS.to_string()
+                ),
+                attr("max_redemption_rate_decrease_bps", "0"),
             ]
         );
-
-        (deps, env, info)
-    }
-
-    // Helper function to create a test Pool object
-    fn get_test_pool(pool_id: u64, sttoken_denom: &str, asset_ordering: AssetOrdering) -> Pool {
-        return Pool {
+        let update_pool_msg = ExecuteMsg::UpdatePool(UpdatePoolMsg {
             pool_id,
-            sttoken_denom: sttoken_denom.to_string(),
-            asset_ordering,
-            last_updated: 0,
-        };
-    }
+            clear_settings: Some(vec![PoolSetting::MaxRedemptionRateDecreaseBps]),
+            ..Default::default()
+        });
+        execute(deps.as_mut(), env.clone(), info, update_pool_msg).unwrap();
 
-    // Helper function to get an add-pool message from a pool object
-    fn get_add_pool_msg(pool_id: u64, pool: Pool) -> crate::msg::ExecuteMsg {
-        return ExecuteMsg::AddPool {
-            pool_id,
-            sttoken_denom: pool.sttoken_denom,
-            asset_ordering: pool.asset_ordering,
-        };
+        let resp =
+            update_scaling_factor_at(&mut deps, &mut env, pool_id, sttoken_denom, "1.089", 8_000);
+        assert_eq!(
+            resp,
+            Err(ContractError::RedemptionRateDecreaseExceedsTolerance {
+                pool_id,
+                previous_redemption_rate: Decimal::from_str("1.09").unwrap(),
+                redemption_rate: Decimal::from_str("1.089").unwrap(),
+            })
+        );
     }
 
     #[test]
-    fn test_instantiate() {
-        let (deps, env, _) = default_instantiate();
+    fn test_pause_unpause() {
+        let pool_id = 1;
+        let sttoken_denom = "stuosmo";
+        let pool = get_test_pool(pool_id, sttoken_denom, AssetOrdering::StTokenFirst);
 
-        // Confirm addresses were set properly
-        let msg = QueryMsg::Config {};
-        let resp = query(deps.as_ref(), env, msg).unwrap();
-        let config: Config = from_binary(&resp).unwrap();
+        let (mut deps, mut env, admin_info) = default_instantiate();
+        let guardian_info = mock_info(GUARDIAN_ADDRESS, &[]);
+        deps.querier.mock_stableswap_pool(pool_id, &pool);
+
+        let add_pool_msg = get_add_pool_msg(pool_id, pool);
+        execute(deps.as_mut(), env.clone(), admin_info.clone(), add_pool_msg).unwrap();
+
+        // The contract should not be paused after instantiation
+        let paused: bool =
+            from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::Paused {}).unwrap()).unwrap();
+        assert!(!paused);
+
+        // Pause all pools as the guardian
+        let pause_msg = ExecuteMsg::Pause { pool_id: None };
+        let resp = execute(deps.as_mut(), env.clone(), guardian_info.clone(), pause_msg).unwrap();
         assert_eq!(
-            config,
-            Config {
-                admin_address: Addr::unchecked(ADMIN_ADDRESS.to_string()),
-                oracle_contract_address: Addr::unchecked(ORACLE_ADDRESS.to_string())
-            }
-        )
-    }
+            resp.attributes,
+            vec![attr("action", "pause"), attr("pool_id", "all")]
+        );
 
-    #[test]
-    fn test_update_config() {
-        let (mut deps, env, info) = default_instantiate();
+        let paused: bool =
+            from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::Paused {}).unwrap()).unwrap();
+        assert!(paused);
 
-        // Update the admin and oracle addresses
-        let updated_admin = "update_admin";
-        let updated_oracle = "updated_oracle";
+        // Updates should fail while paused
+        let resp =
+            update_scaling_factor_at(&mut deps, &mut env, pool_id, sttoken_denom, "1.2", 1_000);
+        assert_eq!(resp, Err(ContractError::Paused {}));
 
-        let update_msg = ExecuteMsg::UpdateConfig {
-            admin_address: updated_admin.to_string(),
-            oracle_contract_address: updated_oracle.to_string(),
-        };
-        let resp = execute(deps.as_mut(), env.clone(), info, update_msg).unwrap();
+        // The guardian should not be able to unpause
+        let unpause_msg = ExecuteMsg::Unpause { pool_id: None };
+        let resp = execute(
+            deps.as_mut(),
+            env.clone(),
+            guardian_info.clone(),
+            unpause_msg.clone(),
+        );
+        assert_eq!(resp, Err(ContractError::Unauthorized {}));
+
+        // Unpause as the admin, and the update should succeed
+        let resp = execute(deps.as_mut(), env.clone(), admin_info.clone(), unpause_msg).unwrap();
         assert_eq!(
             resp.attributes,
-            vec![
-                attr("action", "update_config"),
-                attr("admin_address", updated_admin.to_string()),
-                attr("oracle_contract_address", updated_oracle.to_string()),
-            ]
+            vec![attr("action", "unpause"), attr("pool_id", "all")]
         );
+        update_scaling_factor_at(&mut deps, &mut env, pool_id, sttoken_denom, "1.2", 1_000)
+            .unwrap();
 
-        // Confirm config updated
-        let query_config_msg = QueryMsg::Config {};
-        let query_resp = query(deps.as_ref(), env, query_config_msg).unwrap();
-        let updated_config: Config = from_binary(&query_resp).unwrap();
+        // Pause just the pool as the guardian
+        let pause_msg = ExecuteMsg::Pause {
+            pool_id: Some(pool_id),
+        };
+        let resp = execute(deps.as_mut(), env.clone(), guardian_info.clone(), pause_msg).unwrap();
         assert_eq!(
-            updated_config,
-            Config {
-                admin_address: Addr::unchecked(updated_admin.to_string()),
-                oracle_contract_address: Addr::unchecked(updated_oracle.to_string())
-            }
-        )
+            resp.attributes,
+            vec![attr("action", "pause"), attr("pool_id", "1")]
+        );
+
+        let query_resp = query(deps.as_ref(), env.clone(), QueryMsg::Pool { pool_id }).unwrap();
+        let queried_pool: Pool = from_binary(&query_resp).unwrap();
+        assert!(queried_pool.paused);
+
+        // Updates to the pool should fail, and the guardian should not be able to unpause it
+        let resp =
+            update_scaling_factor_at(&mut deps, &mut env, pool_id, sttoken_denom, "1.21", 2_000);
+        assert_eq!(resp, Err(ContractError::Paused {}));
+
+        let unpause_msg = ExecuteMsg::Unpause {
+            pool_id: Some(pool_id),
+        };
+        let resp = execute(
+            deps.as_mut(),
+            env.clone(),
+            guardian_info.clone(),
+            unpause_msg.clone(),
+        );
+        assert_eq!(resp, Err(ContractError::Unauthorized {}));
+
+        // Unpause the pool as the admin, and the update should succeed
+        execute(deps.as_mut(), env.clone(), admin_info.clone(), unpause_msg).unwrap();
+        update_scaling_factor_at(&mut deps, &mut env, pool_id, sttoken_denom, "1.21", 2_000)
+            .unwrap();
+
+        // Attempt to pause a pool that does not exist
+        let pause_msg = ExecuteMsg::Pause { pool_id: Some(2) };
+        let resp = execute(deps.as_mut(), env.clone(), admin_info, pause_msg);
+        assert_eq!(resp, Err(ContractError::PoolNotFound { pool_id: 2 }));
+
+        // Attempt to pause from an address that is neither the admin nor guardian
+        let pause_msg = ExecuteMsg::Pause { pool_id: None };
+        let resp = execute(deps.as_mut(), env, mock_info("not_admin", &[]), pause_msg);
+        assert_eq!(resp, Err(ContractError::Unauthorized {}));
     }
 
     #[test]
-    fn test_add_remove_pools() {
-        let (mut deps, env, info) = default_instantiate();
+    fn test_sudo_adjust_scaling_factor() {
+        let (mut deps, mut env, info) = default_instantiate();
+        let block_time = 1_000_000;
+        env.block.time = Timestamp::from_seconds(block_time);
 
-        // Create 3 dummy pools
-        let pool1 = get_test_pool(1, "stA", AssetOrdering::StTokenFirst);
-        let pool2 = get_test_pool(2, "stB", AssetOrdering::NativeTokenFirst);
-        let pool3 = get_test_pool(3, "stC", AssetOrdering::StTokenFirst);
+        // Attempt to adjust the scaling factors of a pool that's not registered
+        let adjust_msg = SudoMsg::AdjustScalingFactors {
+            pool_id: 2,
+            scaling_factors: vec![1, 1],
+            max_oracle_deviation_bps: None,
+        };
+        let adjust_resp = sudo(deps.as_mut(), env.clone(), adjust_msg);
+        assert_eq!(adjust_resp, Err(ContractError::PoolNotFound { pool_id: 2 }));
 
-        // Mock each pool in the querier
-        deps.querier.mock_stableswap_pool(1, &pool1);
-        deps.querier.mock_stableswap_pool(2, &pool2);
-        deps.querier.mock_stableswap_pool(3, &pool3);
+        // Register the pool
+        let pool_id = 2;
+        let sttoken_denom = "stuosmo";
+        let pool = get_test_pool(pool_id, sttoken_denom, AssetOrdering::StTokenFirst);
+        deps.querier.mock_stableswap_pool(pool_id, &pool);
+        let add_pool_msg = get_add_pool_msg(pool_id, pool);
+        execute(deps.as_mut(), env.clone(), info, add_pool_msg).unwrap();
 
-        // Add each pool, and confirm the attributes and pool-query for each
-        for pool in vec![pool1.clone(), pool2.clone(), pool3.clone()] {
-            let add_msg = get_add_pool_msg(pool.pool_id, pool.clone());
-            let add_msg_resp = execute(deps.as_mut(), env.clone(), info.clone(), add_msg).unwrap();
+        // Attempt to submit a scaling factor array that doesn't line up with the pool's assets
+        let adjust_msg = SudoMsg::AdjustScalingFactors {
+            pool_id,
+            scaling_factors: vec![1],
+            max_oracle_deviation_bps: None,
+        };
+        let adjust_resp = sudo(deps.as_mut(), env.clone(), adjust_msg);
+        assert_eq!(
+            adjust_resp,
+            Err(ContractError::InvalidNumberOfScalingFactors {
+                pool_id,
+                number: 1,
+                expected: 2
+            })
+        );
 
-            assert_eq!(
-                add_msg_resp.attributes,
-                vec![
-                    attr("action", "add_pool"),
-                    attr("pool_id", pool.pool_id.to_string()),
-                    attr("pool_sttoken_denom", pool.sttoken_denom.clone()),
-                    attr("pool_asset_ordering", pool.asset_ordering.to_string()),
-                ]
-            );
+        // Attempt to submit a zero scaling factor
+        let adjust_msg = SudoMsg::AdjustScalingFactors {
+            pool_id,
+            scaling_factors: vec![100000, 0],
+            max_oracle_deviation_bps: None,
+        };
+        let adjust_resp = sudo(deps.as_mut(), env.clone(), adjust_msg);
+        assert_eq!(
+            adjust_resp,
+            Err(ContractError::ZeroScalingFactor { index: 1 })
+        );
 
-            let query_pool_msg = QueryMsg::Pool {
-                pool_id: pool.pool_id,
-            };
-            let query_resp = query(deps.as_ref(), env.clone(), query_pool_msg).unwrap();
-            let pool_resp: Pool = from_binary(&query_resp).unwrap();
+        // Submit adjust scaling factor message
+        let adjust_msg = SudoMsg::AdjustScalingFactors {
+            pool_id,
+            scaling_factors: vec![1, 1],
+            max_oracle_deviation_bps: None,
+        };
+        let adjust_resp = sudo(deps.as_mut(), env.clone(), adjust_msg).unwrap();
 
-            assert_eq!(pool_resp, pool);
+        assert_eq!(
+            adjust_resp.attributes,
+            vec![
+                attr("action", "sudo_adjust_scaling_factors"),
+                attr("pool_id", "2"),
+                attr("scaling_factors", "[1, 1]"),
+            ]
+        );
+
+        // Confirm the osmosis tx was appended to the message response
+        let expected_adjust_msg: CosmosMsg = MsgStableSwapAdjustScalingFactors {
+            sender: env.contract.address.to_string(),
+            pool_id: 2,
+            scaling_factors: vec![1, 1],
         }
+        .into();
 
-        // Test the AllPools query
-        let all_pools_query_msg = QueryMsg::AllPools {};
-        let query_pools_resp = query(deps.as_ref(), env.clone(), all_pools_query_msg).unwrap();
-        let all_pools_resp: Pools = from_binary(&query_pools_resp).unwrap();
+        assert_eq!(adjust_resp.messages.len(), 1);
+        assert_eq!(adjust_resp.messages[0].msg, expected_adjust_msg);
 
+        // Confirm the override was recorded on the pool
+        let query_resp = query(deps.as_ref(), env.clone(), QueryMsg::Pool { pool_id }).unwrap();
+        let pool: Pool = from_binary(&query_resp).unwrap();
+        assert_eq!(pool.last_scaling_factors, vec![1, 1]);
         assert_eq!(
-            all_pools_resp,
-            Pools {
-                pools: vec![pool1.clone(), pool2, pool3.clone()]
-            }
+            pool.scaling_factor_override,
+            Some(ScalingFactorOverride {
+                scaling_factors: vec![1, 1],
+                time: block_time,
+            })
         );
 
-        // Remove pool 2
-        let remove_pool_msg = ExecuteMsg::RemovePool { pool_id: 2 };
-        let remove_pool_resp =
-            execute(deps.as_mut(), env.clone(), info.clone(), remove_pool_msg).unwrap();
-
+        // With a max deviation, a stale redemption rate can't be used as a reference
+        deps.querier.mock_oracle_redemption_rate(
+            sttoken_denom.to_string(),
+            Decimal::from_str("1.2").unwrap(),
+            block_time - MAX_ORACLE_STALENESS_SECONDS - 1,
+        );
+        let adjust_msg = SudoMsg::AdjustScalingFactors {
+            pool_id,
+            scaling_factors: vec![100000, 120000],
+            max_oracle_deviation_bps: Some(500),
+        };
+        let adjust_resp = sudo(deps.as_mut(), env.clone(), adjust_msg);
         assert_eq!(
-            remove_pool_resp.attributes,
-            vec![attr("action", "remove_pool"), attr("pool_id", "2")]
+            adjust_resp,
+            Err(ContractError::StaleRedemptionRate {
+                token: sttoken_denom.to_string(),
+                age: MAX_ORACLE_STALENESS_SECONDS + 1,
+                max_staleness: MAX_ORACLE_STALENESS_SECONDS,
+            })
         );
 
-        // Query AllPools again, it should only return pools 1 and 3
-        let all_pools_query_msg = QueryMsg::AllPools {};
-        let query_pools_resp = query(deps.as_ref(), env.clone(), all_pools_query_msg).unwrap();
-        let all_pools_resp: Pools = from_binary(&query_pools_resp).unwrap();
-
+        // With a max deviation, scaling factors too far from the oracle's should be rejected
+        deps.querier.mock_oracle_redemption_rate(
+            sttoken_denom.to_string(),
+            Decimal::from_str("1.2").unwrap(),
+            block_time,
+        );
+        let adjust_msg = SudoMsg::AdjustScalingFactors {
+            pool_id,
+            scaling_factors: vec![100000, 130000],
+            max_oracle_deviation_bps: Some(500),
+        };
+        let adjust_resp = sudo(deps.as_mut(), env.clone(), adjust_msg);
         assert_eq!(
-            all_pools_resp,
-            Pools {
-                pools: vec![pool1, pool3]
-            }
+            adjust_resp,
+            Err(ContractError::ScalingFactorsDeviateFromOracle {
+                scaling_factors: "[100000, 130000]".to_string(),
+                oracle_scaling_factors: "[100000, 120000]".to_string(),
+                max_deviation_bps: 500,
+            })
         );
 
-        // Try to add pool 1 again, it should fail
-        let add_duplicate_pool_msg = ExecuteMsg::AddPool {
-            pool_id: 1,
-            sttoken_denom: "".to_string(),
-            asset_ordering: AssetOrdering::StTokenFirst,
+        // Scaling factors within the max deviation should be applied
+        let adjust_msg = SudoMsg::AdjustScalingFactors {
+            pool_id,
+            scaling_factors: vec![100000, 121000],
+            max_oracle_deviation_bps: Some(100),
         };
-        let add_duplicate_pool_resp = execute(deps.as_mut(), env, info, add_duplicate_pool_msg);
+        let adjust_resp = sudo(deps.as_mut(), env.clone(), adjust_msg).unwrap();
         assert_eq!(
-            add_duplicate_pool_resp,
-            Err(ContractError::PoolAlreadyExists { pool_id: 1 })
+            adjust_resp.attributes,
+            vec![
+                attr("action", "sudo_adjust_scaling_factors"),
+                attr("pool_id", "2"),
+                attr("max_oracle_deviation_bps", "100"),
+                attr("oracle_scaling_factors", "[100000, 120000]"),
+                attr("scaling_factors", "[100000, 121000]"),
+            ]
+        );
+
+        // The next oracle update should replace the override
+        let update_resp = update_scaling_factor_at(
+            &mut deps,
+            &mut env,
+            pool_id,
+            sttoken_denom,
+            "1.2",
+            block_time + 100,
         )
+        .unwrap();
+        assert_eq!(
+            update_resp.attributes,
+            vec![
+                attr("action", "update_scaling_factor"),
+                attr("pool_id", "2"),
+                attr("redemption_rate", "1.2"),
+                attr("overridden_scaling_factors", "[100000, 121000]"),
+                attr("scaling_factors", "[100000, 120000]"),
+            ]
+        );
+
+        let query_resp = query(deps.as_ref(), env, QueryMsg::Pool { pool_id }).unwrap();
+        let pool: Pool = from_binary(&query_resp).unwrap();
+        assert_eq!(pool.last_scaling_factors, vec![100000, 120000]);
+        assert_eq!(pool.scaling_factor_override, None);
     }
 
     #[test]
-    fn test_add_misconfigured_pool_id_mismatch() {
-        let (mut deps, env, info) = default_instantiate();
+    fn test_sudo() {
+        let (mut deps, env, admin_info) = default_instantiate();
 
-        let queried_id = 1;
-        let misconfigured_pool_id = 999;
+        // Register a pool
+        let pool_id = 1;
+        let pool = get_test_pool(pool_id, "sttoken", AssetOrdering::StTokenFirst);
+        deps.querier.mock_stableswap_pool(pool_id, &pool);
+        let add_pool_msg = get_add_pool_msg(pool_id, pool);
+        execute(deps.as_mut(), env.clone(), admin_info.clone(), add_pool_msg).unwrap();
 
-        // Create a pool configuration and message
-        let pool = get_test_pool(queried_id, "sttoken", AssetOrdering::StTokenFirst);
-        let add_msg = get_add_pool_msg(queried_id, pool.clone());
+        // Start an admin transfer that will be cancelled when the config is replaced
+        let propose_msg = ExecuteMsg::ProposeAdmin {
+            admin_address: "proposed_admin".to_string(),
+            expires_in_seconds: None,
+        };
+        execute(deps.as_mut(), env.clone(), admin_info, propose_msg).unwrap();
 
-        // Mock out the query response so that the returned pool has a different pool ID
-        deps.querier.mock_invalid_stableswap_pool(
-            queried_id,
-            StableswapPool {
-                id: misconfigured_pool_id,
-                ..Default::default()
+        // Replace the config through governance
+        let replace_config_msg = SudoMsg::ReplaceConfig(InstantiateMsg {
+            admin_address: "governance_admin".to_string(),
+            guardian_address: "governance_guardian".to_string(),
+            oracle_contract_address: "governance_oracle".to_string(),
+            max_oracle_staleness_seconds: 3_600,
+            min_update_interval_seconds: 60,
+            max_redemption_rate_offset_bps: 50,
+            override_delay_seconds: 7_200,
+            oracle_quorum: 2,
+            max_redemption_rate_decrease_bps: 50,
+        });
+        let resp = sudo(deps.as_mut(), env.clone(), replace_config_msg).unwrap();
+        assert_eq!(
+            resp.attributes,
+            vec![
+                attr("action", "replace_config"),
+                attr("admin_address", "governance_admin"),
+                attr("guardian_address", "governance_guardian"),
+                attr("oracle_contract_address", "governance_oracle"),
+                attr("max_oracle_staleness_seconds", "3600"),
+                attr("min_update_interval_seconds", "60"),
+                attr("max_redemption_rate_offset_bps", "50"),
+                attr("override_delay_seconds", "7200"),
+                attr("oracle_quorum", "2"),
+                attr("max_redemption_rate_decrease_bps", "50"),
+            ]
+        );
+
+        let query_resp = query(deps.as_ref(), env.clone(), QueryMsg::Config {}).unwrap();
+        let config: Config = from_binary(&query_resp).unwrap();
+        assert_eq!(
+            config,
+            Config {
+                admin_address: Addr::unchecked("governance_admin"),
+                oracle_contract_address: Addr::unchecked("governance_oracle"),
+                max_oracle_staleness_seconds: 3_600,
+                guardian_address: Addr::unchecked("governance_guardian"),
+                min_update_interval_seconds: 60,
+                max_redemption_rate_offset_bps: 50,
+                override_delay_seconds: 7_200,
+                oracle_quorum: 2,
+                max_redemption_rate_decrease_bps: 50,
+            }
+        );
+
+        let query_resp = query(deps.as_ref(), env.clone(), QueryMsg::PendingAdmin {}).unwrap();
+        let pending_admin: Option<PendingAdmin> = from_binary(&query_resp).unwrap();
+        assert_eq!(pending_admin, None);
+
+        // Pause and unpause the pool through governance
+        let resp = sudo(
+            deps.as_mut(),
+            env.clone(),
+            SudoMsg::Pause {
+                pool_id: Some(pool_id),
             },
+        )
+        .unwrap();
+        assert_eq!(
+            resp.attributes,
+            vec![attr("action", "pause"), attr("pool_id", "1")]
+        );
+
+        let query_resp = query(deps.as_ref(), env.clone(), QueryMsg::Pool { pool_id }).unwrap();
+        let pool: Pool = from_binary(&query_resp).unwrap();
+        assert!(pool.paused);
+
+        let resp = sudo(
+            deps.as_mut(),
+            env.clone(),
+            SudoMsg::Unpause {
+                pool_id: Some(pool_id),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            resp.attributes,
+            vec![attr("action", "unpause"), attr("pool_id", "1")]
         );
 
-        // Attempt to add the pool, it should error since the ID does not match
-        let resp = execute(deps.as_mut(), env.clone(), info.clone(), add_msg);
+        // Force the scaling factors through governance
+        let adjust_msg = SudoMsg::AdjustScalingFactors {
+            pool_id,
+            scaling_factors: vec![100000, 105000],
+            max_oracle_deviation_bps: None,
+        };
+        let resp = sudo(deps.as_mut(), env.clone(), adjust_msg).unwrap();
         assert_eq!(
-            resp,
-            Err(ContractError::PoolNotFoundOsmosis {
-                pool_id: queried_id
-            })
+            resp.attributes,
+            vec![
+                attr("action", "sudo_adjust_scaling_factors"),
+                attr("pool_id", "1"),
+                attr("scaling_factors", "[100000, 105000]"),
+            ]
+        );
+
+        let expected_adjust_msg: CosmosMsg = MsgStableSwapAdjustScalingFactors {
+            sender: env.contract.address.to_string(),
+            pool_id,
+            scaling_factors: vec![100000, 105000],
+        }
+        .into();
+        assert_eq!(resp.messages.len(), 1);
+        assert_eq!(resp.messages[0].msg, expected_adjust_msg);
+
+        // Remove the pool through governance
+        let resp = sudo(deps.as_mut(), env.clone(), SudoMsg::RemovePool { pool_id }).unwrap();
+        assert_eq!(
+            resp.attributes,
+            vec![attr("action", "remove_pool"), attr("pool_id", "1")]
         );
+
+        let resp = sudo(deps.as_mut(), env, SudoMsg::RemovePool { pool_id });
+        assert_eq!(resp, Err(ContractError::PoolNotFound { pool_id }));
     }
 
     #[test]
-    fn test_add_misconfigured_pool_number_of_assets() {
-        let (mut deps, env, info) = default_instantiate();
+    fn test_scaling_factor_override_timelock() {
+        let (mut deps, mut env, admin_info) = default_instantiate();
+        let block_time = 1_000_000;
+        env.block.time = Timestamp::from_seconds(block_time);
 
-        // Create a pool configuration and message
+        // Register a pool
         let pool_id = 1;
-        let pool = get_test_pool(pool_id, "sttoken", AssetOrdering::StTokenFirst);
-        let add_msg = get_add_pool_msg(pool_id, pool.clone());
+        let sttoken_denom = "sttoken";
+        let pool = get_test_pool(pool_id, sttoken_denom, AssetOrdering::StTokenFirst);
+        deps.querier.mock_stableswap_pool(pool_id, &pool);
+        let add_pool_msg = get_add_pool_msg(pool_id, pool);
+        execute(deps.as_mut(), env.clone(), admin_info.clone(), add_pool_msg).unwrap();
 
-        // Mock out the query response so that the returned pool has a more than 2 assets
-        deps.querier.mock_invalid_stableswap_pool(
+        // Only the admin can propose an override
+        let propose_msg = ExecuteMsg::ProposeScalingFactorOverride {
             pool_id,
-            StableswapPool {
-                id: pool_id,
-                pool_liquidity: vec![
-                    Coin {
-                        denom: "denom1".to_string(),
-                        amount: "1000000".to_string(),
-                    },
-                    Coin {
-                        denom: "denom2".to_string(),
-                        amount: "1000000".to_string(),
-                    },
-                    Coin {
-                        denom: "denom3".to_string(),
-                        amount: "1000000".to_string(),
-                    },
-                ],
-                ..Default::default()
-            },
+            scaling_factors: vec![100000, 110000],
+            max_oracle_deviation_bps: None,
+        };
+        let guardian_info = mock_info(GUARDIAN_ADDRESS, &[]);
+        let resp = execute(
+            deps.as_mut(),
+            env.clone(),
+            guardian_info.clone(),
+            propose_msg.clone(),
         );
+        assert_eq!(resp, Err(ContractError::Unauthorized {}));
 
-        // Attempt to add the pool, it should error since there are more than two assets
-        let resp = execute(deps.as_mut(), env.clone(), info.clone(), add_msg);
+        // The scaling factors are validated when the override is proposed
+        let invalid_propose_msg = ExecuteMsg::ProposeScalingFactorOverride {
+            pool_id,
+            scaling_factors: vec![100000],
+            max_oracle_deviation_bps: None,
+        };
+        let resp = execute(
+            deps.as_mut(),
+            env.clone(),
+            admin_info.clone(),
+            invalid_propose_msg,
+        );
         assert_eq!(
             resp,
-            Err(ContractError::InvalidNumberOfPoolAssets { number: 3 })
+            Err(ContractError::InvalidNumberOfScalingFactors {
+                pool_id,
+                number: 1,
+                expected: 2
+            })
         );
-    }
-
-    #[test]
-    fn test_add_misconfigured_pool_asset_ordering() {
-        let (mut deps, env, info) = default_instantiate();
-
-        // Create two pools, one with stToken first, and the other with the stToken second
-        let pool1 = get_test_pool(1, "sttoken", AssetOrdering::StTokenFirst);
-        let pool2 = get_test_pool(2, "sttoken", AssetOrdering::NativeTokenFirst);
-
-        // Mock those two pools out in the query response
-        deps.querier.mock_stableswap_pool(1, &pool1);
-        deps.querier.mock_stableswap_pool(2, &pool2);
 
-        // Create the add messages, but swap the pool IDs (i.e. add_msg1 adds pool ID 2)
-        let add_msg1 = get_add_pool_msg(2, pool1.clone());
-        let add_msg2 = get_add_pool_msg(1, pool2.clone());
+        // Propose the override
+        let executable_time = block_time + OVERRIDE_DELAY_SECONDS;
+        let resp = execute(
+            deps.as_mut(),
+            env.clone(),
+            admin_info.clone(),
+            propose_msg.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            resp.attributes,
+            vec![
+                attr("action", "propose_scaling_factor_override"),
+                attr("pool_id", "1"),
+                attr("scaling_factors", "[100000, 110000]"),
+                attr("executable_time", executable_time.to_string()),
+            ]
+        );
 
-        // Attempt to add these two pools, they should both fail since the asset ordering is incorrect
-        let add_resp1 = execute(deps.as_mut(), env.clone(), info.clone(), add_msg1);
-        assert_eq!(add_resp1, Err(ContractError::InvalidPoolAssetOrdering {}));
+        // Confirm the override is pending
+        let query_msg = QueryMsg::PendingScalingFactorOverrides {};
+        let query_resp = query(deps.as_ref(), env.clone(), query_msg.clone()).unwrap();
+        let pending: PendingScalingFactorOverrides = from_binary(&query_resp).unwrap();
+        assert_eq!(
+            pending,
+            PendingScalingFactorOverrides {
+                overrides: vec![PendingScalingFactorOverride {
+                    pool_id,
+                    scaling_factors: vec![100000, 110000],
+                    max_oracle_deviation_bps: None,
+                    proposed_time: block_time,
+                    executable_time,
+                }]
+            }
+        );
 
-        let add_resp2 = execute(deps.as_mut(), env.clone(), info.clone(), add_msg2);
-        assert_eq!(add_resp2, Err(ContractError::InvalidPoolAssetOrdering {}));
-    }
+        // The override cannot be executed before the delay has passed
+        env.block.time = Timestamp::from_seconds(executable_time - 1);
+        let execute_msg = ExecuteMsg::ExecuteScalingFactorOverride { pool_id };
+        let resp = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("keeper", &[]),
+            execute_msg.clone(),
+        );
+        assert_eq!(
+            resp,
+            Err(ContractError::ScalingFactorOverrideNotExecutable {
+                pool_id,
+                executable_time
+            })
+        );
 
-    #[test]
-    fn test_unauthorized() {
-        let (mut deps, env, _) = default_instantiate();
+        // Only the admin or guardian can cancel the override
+        let cancel_msg = ExecuteMsg::CancelScalingFactorOverride { pool_id };
+        let resp = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("not_admin", &[]),
+            cancel_msg.clone(),
+        );
+        assert_eq!(resp, Err(ContractError::Unauthorized {}));
 
-        // Create info with non-admin sender
-        let invalid_info: MessageInfo = mock_info("not_admin", &[]);
+        // Cancel the override from the guardian
+        let resp = execute(
+            deps.as_mut(),
+            env.clone(),
+            guardian_info,
+            cancel_msg.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            resp.attributes,
+            vec![
+                attr("action", "cancel_scaling_factor_override"),
+                attr("pool_id", "1"),
+            ]
+        );
 
-        // Attempt to add the pool with a non-admin address
-        let pool_id = 1;
-        let pool = get_test_pool(pool_id, "stA", AssetOrdering::StTokenFirst);
-        let add_msg = get_add_pool_msg(pool_id, pool);
-        let add_resp = execute(deps.as_mut(), env.clone(), invalid_info.clone(), add_msg);
+        // Once cancelled, the override can no longer be executed or cancelled
+        env.block.time = Timestamp::from_seconds(executable_time);
+        let resp = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("keeper", &[]),
+            execute_msg.clone(),
+        );
+        assert_eq!(
+            resp,
+            Err(ContractError::NoPendingScalingFactorOverride { pool_id })
+        );
+        let resp = execute(deps.as_mut(), env.clone(), admin_info.clone(), cancel_msg);
+        assert_eq!(
+            resp,
+            Err(ContractError::NoPendingScalingFactorOverride { pool_id })
+        );
 
-        assert_eq!(add_resp, Err(ContractError::Unauthorized {}));
+        // Propose the override again and execute it from any address after the delay
+        let executable_time = executable_time + OVERRIDE_DELAY_SECONDS;
+        execute(deps.as_mut(), env.clone(), admin_info, propose_msg).unwrap();
 
-        // Attempt to remove a pool with a non-admin address
-        let remove_msg = ExecuteMsg::RemovePool { pool_id: 1 };
-        let remove_resp = execute(deps.as_mut(), env.clone(), invalid_info.clone(), remove_msg);
+        env.block.time = Timestamp::from_seconds(executable_time);
+        let resp = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("keeper", &[]),
+            execute_msg,
+        )
+        .unwrap();
+        assert_eq!(
+            resp.attributes,
+            vec![
+                attr("action", "execute_scaling_factor_override"),
+                attr("pool_id", "1"),
+                attr("scaling_factors", "[100000, 110000]"),
+            ]
+        );
 
-        assert_eq!(remove_resp, Err(ContractError::Unauthorized {}));
+        let expected_adjust_msg: CosmosMsg = MsgStableSwapAdjustScalingFactors {
+            sender: env.contract.address.to_string(),
+            pool_id,
+            scaling_factors: vec![100000, 110000],
+        }
+        .into();
+        assert_eq!(resp.messages.len(), 1);
+        assert_eq!(resp.messages[0].msg, expected_adjust_msg);
 
-        // Attempt to update the scaling factor of a pool with a non-admin address
-        let adjust_msg = ExecuteMsg::SudoAdjustScalingFactors {
-            pool_id: 1,
-            scaling_factors: vec![1, 1],
-        };
-        let adjust_resp = execute(deps.as_mut(), env, invalid_info, adjust_msg);
+        // Confirm the override was recorded on the pool and is no longer pending
+        let query_resp = query(deps.as_ref(), env.clone(), QueryMsg::Pool { pool_id }).unwrap();
+        let pool: Pool = from_binary(&query_resp).unwrap();
+        assert_eq!(
+            pool.scaling_factor_override,
+            Some(ScalingFactorOverride {
+                scaling_factors: vec![100000, 110000],
+                time: executable_time,
+            })
+        );
 
-        assert_eq!(adjust_resp, Err(ContractError::Unauthorized {}));
+        let query_resp = query(deps.as_ref(), env, query_msg).unwrap();
+        let pending: PendingScalingFactorOverrides = from_binary(&query_resp).unwrap();
+        assert_eq!(pending.overrides, vec![]);
     }
 
     #[test]
-    fn test_update_scaling_factor() {
-        let pool_id = 2;
-        let sttoken_denom = "stuosmo";
-        let asset_ordering = AssetOrdering::StTokenFirst;
-        let pool = get_test_pool(pool_id, sttoken_denom, asset_ordering);
-
-        let block_time = 1_000_000;
-        let redemption_rate = Decimal::from_str("1.2").unwrap();
-        let expected_scaling_factors = vec![100000, 120000];
-
-        // Mock out the block time and the oracle query response
-        let (mut deps, mut env, info) = default_instantiate();
-        env.block.time = Timestamp::from_seconds(block_time);
-        deps.querier
-            .mock_oracle_redemption_rate(sttoken_denom.to_string(), redemption_rate);
-
-        // Mock out the stableswap pool on Osmosis
-        deps.querier.mock_stableswap_pool(pool_id, &pool);
-
-        // Add a pool
-        let add_pool_msg = get_add_pool_msg(pool_id, pool);
-        execute(deps.as_mut(), env.clone(), info.clone(), add_pool_msg).unwrap();
+    fn test_migrate_from_v1_0_0() {
+        let mut deps = OwnedDeps {
+            storage: MockStorage::default(),
+            api: MockApi::default(),
+            querier: WasmMockQuerier::new(),
+            custom_query_type: Default::default(),
+        };
+        let env = mock_env();
 
-        // Update the scaling factor
-        let update_msg = ExecuteMsg::UpdateScalingFactor { pool_id: 2 };
-        let update_pool_resp =
-            execute(deps.as_mut(), env.clone(), info.clone(), update_msg).unwrap();
+        // Store the config and pools in the v1.0.0 layout
+        cw2::set_contract_version(&mut deps.storage, CONTRACT_NAME, "1.0.0").unwrap();
+        v1_0_0::CONFIG
+            .save(
+                &mut deps.storage,
+                &v1_0_0::Config {
+                    admin_address: Addr::unchecked(ADMIN_ADDRESS),
+                    oracle_contract_address: Addr::unchecked(ORACLE_ADDRESS),
+                },
+            )
+            .unwrap();
+        for (pool_id, sttoken_denom, asset_ordering) in [
+            (1, "ibc/stuosmo", AssetOrdering::StTokenFirst),
+            (2, "ibc/stujuno", AssetOrdering::NativeTokenFirst),
+        ] {
+            let legacy_pool = v1_0_0::Pool {
+                pool_id,
+                sttoken_denom: sttoken_denom.to_string(),
+                asset_ordering,
+                last_updated: 100,
+            };
+            v1_0_0::POOLS
+                .save(&mut deps.storage, pool_id, &legacy_pool)
+                .unwrap();
+        }
 
-        // Confrim attributes
+        // Migrate without specifying a guardian, so it should default to the admin
+        let migrate_msg = MigrateMsg {
+            guardian_address: None,
+            max_oracle_staleness_seconds: None,
+        };
+        let resp = migrate(deps.as_mut(), env.clone(), migrate_msg).unwrap();
         assert_eq!(
-            update_pool_resp.attributes,
+            resp.attributes,
             vec![
-                attr("action", "update_scaling_factor"),
-                attr("pool_id", "2"),
-                attr("redemption_rate", "1.2"),
-                attr("scaling_factors", "[100000, 120000]")
+                attr("action", "migrate"),
+                attr("previous_version", "1.0.0"),
+                attr("version", CONTRACT_VERSION),
             ]
         );
 
-        // Confirm pool was updated with the current block time
-        let query_pool_msg = QueryMsg::Pool { pool_id: 2 };
-        let query_pool_resp = query(deps.as_ref(), env.clone(), query_pool_msg).unwrap();
+        // Confirm the config was migrated with the defaults
+        let query_resp = query(deps.as_ref(), env.clone(), QueryMsg::Config {}).unwrap();
+        let config: Config = from_binary(&query_resp).unwrap();
+        assert_eq!(
+            config,
+            Config {
+                admin_address: Addr::unchecked(ADMIN_ADDRESS),
+                oracle_contract_address: Addr::unchecked(ORACLE_ADDRESS),
+                max_oracle_staleness_seconds: DEFAULT_MAX_ORACLE_STALENESS_SECONDS,
+                guardian_address: Addr::unchecked(ADMIN_ADDRESS),
+                min_update_interval_seconds: 0,
+                max_redemption_rate_offset_bps: 0,
+                override_delay_seconds: DEFAULT_OVERRIDE_DELAY_SECONDS,
+                oracle_quorum: 1,
+                max_redemption_rate_decrease_bps: 0,
+            }
+        );
 
-        let queried_pool: Pool = from_binary(&query_pool_resp).unwrap();
-        let queried_pool_cloned = queried_pool.clone();
-        let expected_pool = Pool {
-            last_updated: block_time,
-            ..queried_pool_cloned
+        let query_resp = query(deps.as_ref(), env.clone(), QueryMsg::Paused {}).unwrap();
+        let paused: bool = from_binary(&query_resp).unwrap();
+        assert!(!paused);
+
+        // Confirm each pool was migrated and kept its original fields
+        let query_resp = query(deps.as_ref(), env.clone(), QueryMsg::AllPools {}).unwrap();
+        let pools: Pools = from_binary(&query_resp).unwrap();
+        let mut expected_pool_1 = get_test_pool(1, "ibc/stuosmo", AssetOrdering::StTokenFirst);
+        let mut expected_pool_2 = get_test_pool(2, "ibc/stujuno", AssetOrdering::NativeTokenFirst);
+        expected_pool_1.last_updated = 100;
+        expected_pool_2.last_updated = 100;
+        assert_eq!(pools.pools, vec![expected_pool_1, expected_pool_2]);
+
+        // Confirm the contract version was updated
+        let version = cw2::get_contract_version(&deps.storage).unwrap();
+        assert_eq!(version.contract, CONTRACT_NAME);
+        assert_eq!(version.version, CONTRACT_VERSION);
+
+        // Migrating again from the current version should leave the state unchanged
+        let migrate_msg = MigrateMsg {
+            guardian_address: Some(GUARDIAN_ADDRESS.to_string()),
+            max_oracle_staleness_seconds: Some(1),
         };
+        migrate(deps.as_mut(), env.clone(), migrate_msg).unwrap();
 
-        assert_eq!(queried_pool, expected_pool);
+        let query_resp = query(deps.as_ref(), env, QueryMsg::Config {}).unwrap();
+        let migrated_config: Config = from_binary(&query_resp).unwrap();
+        assert_eq!(migrated_config, config);
+    }
 
-        // Confirm the osmosis tx was appended to the message response
-        let expected_update_msg: CosmosMsg = MsgStableSwapAdjustScalingFactors {
-            sender: env.contract.address.to_string(),
-            pool_id: 2,
-            scaling_factors: expected_scaling_factors,
-        }
-        .into();
+    #[test]
+    fn test_migrate_from_v1_0_0_with_guardian() {
+        let mut deps = OwnedDeps {
+            storage: MockStorage::default(),
+            api: MockApi::default(),
+            querier: WasmMockQuerier::new(),
+            custom_query_type: Default::default(),
+        };
 
-        assert_eq!(update_pool_resp.messages.len(), 1);
-        assert_eq!(update_pool_resp.messages[0].msg, expected_update_msg);
+        cw2::set_contract_version(&mut deps.storage, CONTRACT_NAME, "1.0.0").unwrap();
+        v1_0_0::CONFIG
+            .save(
+                &mut deps.storage,
+                &v1_0_0::Config {
+                    admin_address: Addr::unchecked(ADMIN_ADDRESS),
+                    oracle_contract_address: Addr::unchecked(ORACLE_ADDRESS),
+                },
+            )
+            .unwrap();
 
-        // Attempt to update a non-existent pool, it should error
-        let update_msg = ExecuteMsg::UpdateScalingFactor { pool_id: 1 };
-        let update_pool_resp = execute(deps.as_mut(), env.clone(), info, update_msg);
+        let migrate_msg = MigrateMsg {
+            guardian_address: Some(GUARDIAN_ADDRESS.to_string()),
+            max_oracle_staleness_seconds: Some(MAX_ORACLE_STALENESS_SECONDS),
+        };
+        migrate(deps.as_mut(), mock_env(), migrate_msg).unwrap();
+
+        let query_resp = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+        let config: Config = from_binary(&query_resp).unwrap();
+        assert_eq!(config.guardian_address, Addr::unchecked(GUARDIAN_ADDRESS));
         assert_eq!(
-            update_pool_resp,
-            Err(ContractError::PoolNotFound { pool_id: 1 })
+            config.max_oracle_staleness_seconds,
+            MAX_ORACLE_STALENESS_SECONDS
         );
     }
 
     #[test]
-    fn test_sudo_adjust_scaling_factor() {
-        let (mut deps, env, info) = default_instantiate();
-
-        // Submit adjust scaling factor message
-        let adjust_msg = ExecuteMsg::SudoAdjustScalingFactors {
-            pool_id: 2,
-            scaling_factors: vec![1, 1],
+    fn test_migrate_invalid_contract() {
+        let (mut deps, env, _) = default_instantiate();
+        let migrate_msg = MigrateMsg {
+            guardian_address: None,
+            max_oracle_staleness_seconds: None,
         };
-        let adjust_resp = execute(deps.as_mut(), env.clone(), info, adjust_msg).unwrap();
 
+        // Attempt to migrate to an older version
+        cw2::set_contract_version(&mut deps.storage, CONTRACT_NAME, "99.0.0").unwrap();
+        let err = migrate(deps.as_mut(), env.clone(), migrate_msg.clone()).unwrap_err();
         assert_eq!(
-            adjust_resp.attributes,
-            vec![
-                attr("action", "sudo_adjust_scaling_factors"),
-                attr("pool_id", "2"),
-                attr("scaling_factors", "[1,1]"),
-            ]
+            err,
+            ContractError::CannotMigrateToOlderVersion {
+                stored_version: "99.0.0".to_string(),
+                version: CONTRACT_VERSION.to_string(),
+            }
         );
 
-        // Confirm the osmosis tx was appended to the message response
-        let expected_adjust_msg: CosmosMsg = MsgStableSwapAdjustScalingFactors {
-            sender: env.contract.address.to_string(),
-            pool_id: 2,
-            scaling_factors: vec![1, 1],
-        }
-        .into();
-
-        assert_eq!(adjust_resp.messages.len(), 1);
-        assert_eq!(adjust_resp.messages[0].msg, expected_adjust_msg);
+        // Attempt to migrate from a different contract
+        cw2::set_contract_version(&mut deps.storage, "crates.io:other-contract", "1.0.0").unwrap();
+        let err = migrate(deps.as_mut(), env, migrate_msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InvalidContractName {
+                expected: CONTRACT_NAME.to_string(),
+                actual: "crates.io:other-contract".to_string(),
+            }
+        );
     }
 }
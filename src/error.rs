@@ -1,4 +1,4 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{Decimal, StdError};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -9,9 +9,64 @@ pub enum ContractError {
     #[error("Unauthorized")]
     Unauthorized {},
 
+    #[error("Scaling factor updates are paused")]
+    Paused {},
+
+    #[error("There is no pending admin proposal")]
+    NoPendingAdmin {},
+
+    #[error("The admin proposal expired at {expires_at}")]
+    AdminProposalExpired { expires_at: u64 },
+
+    #[error("Cannot migrate from contract {actual}, expected {expected}")]
+    InvalidContractName { expected: String, actual: String },
+
+    #[error("Invalid contract version {version}")]
+    InvalidContractVersion { version: String },
+
+    #[error("Cannot migrate from version {stored_version} to older version {version}")]
+    CannotMigrateToOlderVersion {
+        stored_version: String,
+        version: String,
+    },
+
     #[error("Unable to query redemption rate of {token} from oracle, {error}")]
     UnableToQueryRedemptionRate { token: String, error: String },
 
+    #[error("Redemption rate of {token} is unavailable or zero")]
+    InvalidRedemptionRate { token: String },
+
+    #[error("Redemption rate of {token} is stale, last updated {age} seconds ago (max {max_staleness} seconds)")]
+    StaleRedemptionRate {
+        token: String,
+        age: u64,
+        max_staleness: u64,
+    },
+
+    #[error("Redemption rate of pool {pool_id} decreased from {previous_redemption_rate} to {redemption_rate}, which exceeds the tolerance")]
+    RedemptionRateDecreaseExceedsTolerance {
+        pool_id: u64,
+        previous_redemption_rate: Decimal,
+        redemption_rate: Decimal,
+    },
+
+    #[error("Max redemption rate offset of {max_offset_bps} basis points exceeds the limit of {max_offset_limit_bps} basis points")]
+    InvalidMaxRedemptionRateOffset {
+        max_offset_bps: u64,
+        max_offset_limit_bps: u64,
+    },
+
+    #[error("Redemption rate offset of {offset_bps} basis points exceeds the max of {max_offset_bps} basis points")]
+    RedemptionRateOffsetExceedsMax {
+        offset_bps: i64,
+        max_offset_bps: u64,
+    },
+
+    #[error(
+        "Pool {pool_id} was updated too recently, the next update is allowed at {next_update_time}"
+    )]
+    UpdateTooFrequent { pool_id: u64, next_update_time: u64 },
+
     #[error("Pool {pool_id} is not configured in the contract")]
     PoolNotFound { pool_id: u64 },
 
@@ -21,9 +76,98 @@ pub enum ContractError {
     #[error("Pool {pool_id} not found on Osmosis")]
     PoolNotFoundOsmosis { pool_id: u64 },
 
+    #[error("Denom {denom} is not one of the assets in pool {pool_id}")]
+    DenomNotInPool { pool_id: u64, denom: String },
+
     #[error("The specified asset ordering does not match the underlying pool")]
     InvalidPoolAssetOrdering {},
 
-    #[error("The underlying pool has {number} of assets, only 2 is allowed")]
+    #[error("Only one of the asset ordering or asset scaling factors can be specified")]
+    AssetOrderingAndScalingFactorsSpecified {},
+
+    #[error("{number} asset scaling factors were specified, but the underlying pool has {expected} assets")]
+    InvalidNumberOfAssetScalingFactors { number: u64, expected: u64 },
+
+    #[error("Invalid scaling factor for the asset at index {index} of the pool")]
+    InvalidAssetScalingFactor { index: u64 },
+
+    #[error("Scaling factor multiplier {multiplier} must be between 1 and {max_multiplier}")]
+    InvalidScalingFactorMultiplier {
+        multiplier: u64,
+        max_multiplier: u64,
+    },
+
+    #[error("Scaling factor exceeds the maximum supported value")]
+    ScalingFactorOverflow {},
+
+    #[error("{number} scaling factors were specified, but pool {pool_id} has {expected} assets")]
+    InvalidNumberOfScalingFactors {
+        pool_id: u64,
+        number: u64,
+        expected: u64,
+    },
+
+    #[error("There is no pending scaling factor override for pool {pool_id}")]
+    NoPendingScalingFactorOverride { pool_id: u64 },
+
+    #[error(
+        "The scaling factor override for pool {pool_id} cannot be executed until {executable_time}"
+    )]
+    ScalingFactorOverrideNotExecutable { pool_id: u64, executable_time: u64 },
+
+    #[error("Scaling factor of the asset at index {index} of the pool cannot be zero")]
+    ZeroScalingFactor { index: u64 },
+
+    #[error("Scaling factors {scaling_factors} deviate from the scaling factors implied by the oracle {oracle_scaling_factors} by more than {max_deviation_bps} basis points")]
+    ScalingFactorsDeviateFromOracle {
+        scaling_factors: String,
+        oracle_scaling_factors: String,
+        max_deviation_bps: u64,
+    },
+
+    #[error("Pool setting {setting} cannot be both specified and cleared")]
+    ConflictingPoolSetting { setting: String },
+
+    #[error("Override delay of {override_delay_seconds} seconds is less than the minimum of {min_override_delay_seconds} seconds")]
+    InvalidOverrideDelay {
+        override_delay_seconds: u64,
+        min_override_delay_seconds: u64,
+    },
+
+    #[error("Oracle quorum must be at least 1")]
+    InvalidOracleQuorum {},
+
+    #[error("Oracle {contract_address} is already registered")]
+    OracleSourceAlreadyExists { contract_address: String },
+
+    #[error("Oracle {contract_address} is not registered")]
+    OracleSourceNotFound { contract_address: String },
+
+    #[error("Only {responses} oracles returned a valid redemption rate for {token}, but a quorum of {quorum} is required")]
+    OracleQuorumNotMet {
+        token: String,
+        responses: u64,
+        quorum: u64,
+    },
+
+    #[error(
+        "{number} asset decimals were specified, but the underlying pool has {expected} assets"
+    )]
+    InvalidNumberOfAssetDecimals { number: u64, expected: u64 },
+
+    #[error(
+        "A difference of {decimal_difference} in asset decimals would overflow the scaling factors"
+    )]
+    InvalidAssetDecimals { decimal_difference: u32 },
+
+    #[error("The contract is not the scaling factor controller of pool {pool_id}, the current controller is {scaling_factor_controller}")]
+    NotScalingFactorController {
+        pool_id: u64,
+        scaling_factor_controller: String,
+    },
+
+    #[error(
+        "The underlying pool has {number} assets, which is not supported by the pool configuration"
+    )]
     InvalidNumberOfPoolAssets { number: u64 },
 }
@@ -1,69 +1,602 @@
-use cosmwasm_std::Decimal;
+use std::collections::HashMap;
 
-use crate::{state::AssetOrdering, ContractError};
+use cosmwasm_std::{Decimal, Uint128};
+
+use crate::{
+    msg::{PythPrice, RedemptionRateResponse},
+    state::{
+        AssetOrdering, AssetScalingFactor, RedemptionRateSample, RoundingMode, ScalingFactorRamp,
+    },
+    ContractError,
+};
 use osmosis_std::types::osmosis::gamm::poolmodels::stableswap::v1beta1::Pool as StableswapPool;
 
+/// The default multiplier used to convert redemption rates into scaling factors,
+/// giving five decimals of precision
+pub const DEFAULT_SCALING_FACTOR_MULTIPLIER: u64 = 100_000;
+
+/// The largest redemption rate (or ratio of redemption rates) that a pool's scaling factor
+/// multiplier must support without overflowing a u64
+pub const MAX_SUPPORTED_REDEMPTION_RATE: u64 = 100;
+
+/// The number of redemption rates retained in each stToken's redemption rate history
+pub const MAX_REDEMPTION_RATE_SAMPLES: usize = 48;
+
+/// The largest max redemption rate offset (in basis points) that can be configured, so that
+/// the admin can't move the scaling factors away from the redemption rate without going
+/// through a scaling factor override
+pub const MAX_REDEMPTION_RATE_OFFSET_LIMIT_BPS: u64 = 500;
+
+/// The minimum delay (in seconds) before a proposed scaling factor override can be executed,
+/// so that the guardian always has a window to cancel it
+pub const MIN_OVERRIDE_DELAY_SECONDS: u64 = 3_600;
+
 /// Converts an stToken redemption rate (i.e. exchange rate) into a scaling factors array
 ///
 /// stTokens trade above their corresponding native tokens since they have rewards associated with them
 /// As a result, the stToken pool composition consists of a larger volume of stTokens and
 /// the native tokens should be scaled up accordingly
 ///
-/// The scaling factors array consists of integers that give a ratio of the two assets
+/// The scaling factors array consists of integers that give a ratio of the assets
 /// For instance, a ratio of 1.2 is defined as the array [120000, 100000]
-/// The ordering of the elements in the array correspond with the ordering of the two assets in the pool
-/// which is configured at the time that the pool is registered
+/// The pool's multiplier determines the precision of the integers (e.g. a multiplier of
+/// 100000 keeps five decimals of the redemption rate, whereas 10^8 keeps eight)
+/// The pool's rounding mode determines how any remaining decimals are rounded
+/// The ordering of the elements in the array correspond with the ordering of the assets in the pool,
+/// where each asset's scaling factor is determined by the descriptor at the same index
+/// (which is configured at the time that the pool is registered)
+///   - The stToken (OracleDriven) is not scaled
+///   - The native token (NativeAnchor) is scaled up by the redemption rate
+///   - Fixed assets always use their configured scaling factor
+///   - A paired stToken (PairedStToken) is scaled by the ratio of the stToken's redemption rate
+///     to the paired stToken's redemption rate, since both redeem for the same native token
 ///
 /// Ex1: If the redemption rate is 1.2 and the pool has the native asset listed first,
 ///      the scaling factor should be [120000, 100000]
 /// Ex2: If the redemption rate is 1.2345 and the pool has the stToken listed first,
 ///      the scaling factor should be [100000, 123450]
+/// Ex3: If the redemption rate is 1.2 and the pool is [nativeToken, stToken, fixed(100000)],
+///      the scaling factor should be [120000, 100000, 100000]
+/// Ex4: If the redemption rate is 1.2, and the paired stToken's redemption rate is 1.1,
+///      in a [stToken, pairedStToken] pool, the scaling factor should be [100000, 109090]
+///
+/// The redemption rate of each paired stToken is looked up by denom, and must be non-zero
+/// An error is returned if any scaling factor would overflow a u64
 pub fn convert_redemption_rate_to_scaling_factors(
     redemption_rate: Decimal,
-    asset_ordering: AssetOrdering,
-) -> Vec<u64> {
-    let multiplier_int: u64 = 100_000;
+    asset_scaling_factors: &[AssetScalingFactor],
+    paired_redemption_rates: &HashMap<String, Decimal>,
+    multiplier_int: u64,
+    rounding_mode: &RoundingMode,
+) -> Result<Vec<u64>, ContractError> {
     let multiplier_dec = Decimal::from_ratio(multiplier_int, 1u64);
-    let scaling_factor = (redemption_rate * multiplier_dec).to_uint_floor().u128() as u64;
+    let scaled_redemption_rate = redemption_rate
+        .checked_mul(multiplier_dec)
+        .map_err(|_| ContractError::ScalingFactorOverflow {})?;
+    let scaling_factor = to_scaling_factor(scaled_redemption_rate, rounding_mode)?;
+
+    asset_scaling_factors
+        .iter()
+        .map(|asset| match asset {
+            AssetScalingFactor::OracleDriven {} => Ok(multiplier_int),
+            AssetScalingFactor::NativeAnchor {} => Ok(scaling_factor),
+            AssetScalingFactor::Fixed { scaling_factor } => Ok(*scaling_factor),
+            AssetScalingFactor::PairedStToken { denom, .. } => {
+                let paired_redemption_rate = paired_redemption_rates
+                    .get(denom)
+                    .filter(|redemption_rate| !redemption_rate.is_zero())
+                    .ok_or(ContractError::InvalidRedemptionRate {
+                        token: denom.to_string(),
+                    })?;
+                let cross_ratio = scaled_redemption_rate
+                    .checked_div(*paired_redemption_rate)
+                    .map_err(|_| ContractError::InvalidRedemptionRate {
+                        token: denom.to_string(),
+                    })?;
+                to_scaling_factor(cross_ratio, rounding_mode)
+            }
+        })
+        .collect()
+}
+
+/// Rounds a scaled redemption rate into an integer scaling factor, erroring
+/// if it does not fit in a u64
+fn to_scaling_factor(
+    scaled_redemption_rate: Decimal,
+    rounding_mode: &RoundingMode,
+) -> Result<u64, ContractError> {
+    let floor = scaled_redemption_rate.to_uint_floor();
+    let rounded = match rounding_mode {
+        RoundingMode::Floor => floor,
+        RoundingMode::Ceil => scaled_redemption_rate.to_uint_ceil(),
+        // Round to the nearest integer, with ties rounded to the even neighbor
+        RoundingMode::HalfEven => {
+            let remainder = scaled_redemption_rate - scaled_redemption_rate.floor();
+            let half = Decimal::percent(50);
+            if remainder > half || (remainder == half && floor.u128() % 2 == 1) {
+                floor + Uint128::one()
+            } else {
+                floor
+            }
+        }
+    };
+    u64::try_from(rounded.u128()).map_err(|_| ContractError::ScalingFactorOverflow {})
+}
+
+/// Folds the difference in each asset's decimals into the scaling factors, so that an asset
+/// with more decimals is scaled up by 10^(decimals - min decimals)
+/// This keeps the ratio of the assets' values intact when the pool compares raw amounts
+///   e.g. scaling factors of [100000, 120000] in a pool of a 6 decimal stToken and an
+///        18 decimal native token become [100000, 120000000000000000]
+pub fn normalize_scaling_factors_for_decimals(
+    scaling_factors: Vec<u64>,
+    asset_decimals: &[u32],
+) -> Result<Vec<u64>, ContractError> {
+    let min_decimals = asset_decimals.iter().min().copied().unwrap_or_default();
+    scaling_factors
+        .into_iter()
+        .zip(asset_decimals)
+        .map(|(scaling_factor, decimals)| {
+            10u64
+                .checked_pow(decimals - min_decimals)
+                .and_then(|decimal_multiplier| scaling_factor.checked_mul(decimal_multiplier))
+                .ok_or(ContractError::ScalingFactorOverflow {})
+        })
+        .collect()
+}
+
+/// Validates that there's a decimal exponent for each asset in the pool, and that folding
+/// the difference in decimals into the scaling factors will not overflow for the supported
+/// range of redemption rates
+pub fn validate_asset_decimals(
+    asset_decimals: &[u32],
+    number_of_assets: usize,
+    multiplier: u64,
+) -> Result<(), ContractError> {
+    if asset_decimals.len() != number_of_assets {
+        return Err(ContractError::InvalidNumberOfAssetDecimals {
+            number: asset_decimals.len() as u64,
+            expected: number_of_assets as u64,
+        });
+    }
+
+    let min_decimals = asset_decimals.iter().min().copied().unwrap_or_default();
+    let max_decimals = asset_decimals.iter().max().copied().unwrap_or_default();
+    let decimal_difference = max_decimals - min_decimals;
+    10u64
+        .checked_pow(decimal_difference)
+        .and_then(|decimal_multiplier| decimal_multiplier.checked_mul(multiplier))
+        .and_then(|max_scaling_factor| {
+            max_scaling_factor.checked_mul(MAX_SUPPORTED_REDEMPTION_RATE)
+        })
+        .ok_or(ContractError::InvalidAssetDecimals { decimal_difference })?;
+
+    Ok(())
+}
+
+/// Returns the scaling factors at the given time along a ramp, moving each scaling factor
+/// linearly from its initial value at the start time to its target at the end time
+///   e.g. Halfway through a ramp from [100000, 120000] to [100000, 140000],
+///        the scaling factors are [100000, 130000]
+pub fn interpolate_scaling_factors(ramp: &ScalingFactorRamp, time: u64) -> Vec<u64> {
+    let duration = ramp.end_time.saturating_sub(ramp.start_time);
+    if duration == 0 {
+        return ramp.target_scaling_factors.clone();
+    }
+    let elapsed = time.saturating_sub(ramp.start_time).min(duration);
+
+    ramp.initial_scaling_factors
+        .iter()
+        .zip(&ramp.target_scaling_factors)
+        .map(|(&initial, &target)| {
+            let change =
+                (initial.abs_diff(target) as u128 * elapsed as u128 / duration as u128) as u64;
+            if target >= initial {
+                initial + change
+            } else {
+                initial - change
+            }
+        })
+        .collect()
+}
+
+/// Validates that a pool's scaling factor multiplier is non-zero, and that scaling factors
+/// built from the multiplier will not overflow for the supported range of redemption rates
+pub fn validate_scaling_factor_multiplier(multiplier: u64) -> Result<(), ContractError> {
+    let max_multiplier = u64::MAX / MAX_SUPPORTED_REDEMPTION_RATE;
+    if multiplier == 0 || multiplier > max_multiplier {
+        return Err(ContractError::InvalidScalingFactorMultiplier {
+            multiplier,
+            max_multiplier,
+        });
+    }
+    Ok(())
+}
+
+/// Formats the scaling factor descriptors of a pool for event attributes
+/// (e.g. "[oracle_driven, native_anchor]")
+pub fn format_asset_scaling_factors(asset_scaling_factors: &[AssetScalingFactor]) -> String {
+    let assets: Vec<String> = asset_scaling_factors
+        .iter()
+        .map(|a| a.to_string())
+        .collect();
+    format!("[{}]", assets.join(", "))
+}
+
+/// Formats a scaling factors array for event attributes (e.g. "[100000, 120000]")
+pub fn format_scaling_factors(scaling_factors: &[u64]) -> String {
+    let factors: Vec<String> = scaling_factors.iter().map(|f| f.to_string()).collect();
+    format!("[{}]", factors.join(", "))
+}
+
+/// Checks whether any of the updated scaling factors moved by more than `max_change_bps`
+/// relative to the previously applied scaling factors
+/// If there are no previous scaling factors to compare against (e.g. the first update),
+/// the change is always allowed
+pub fn exceeds_max_scaling_factor_change(
+    previous_scaling_factors: &[u64],
+    updated_scaling_factors: &[u64],
+    max_change_bps: u64,
+) -> bool {
+    if previous_scaling_factors.len() != updated_scaling_factors.len() {
+        return false;
+    }
+
+    previous_scaling_factors
+        .iter()
+        .zip(updated_scaling_factors)
+        .any(|(&previous, &updated)| {
+            let change = previous.abs_diff(updated) as u128;
+            change * 10_000 > previous as u128 * max_change_bps as u128
+        })
+}
+
+/// Checks whether all of the updated scaling factors moved by less than `deadband_bps`
+/// relative to the previously applied scaling factors, in which case the update is
+/// not worth submitting
+/// If there are no previous scaling factors to compare against (e.g. the first update),
+/// the change is never within the deadband
+pub fn within_deadband(
+    previous_scaling_factors: &[u64],
+    updated_scaling_factors: &[u64],
+    deadband_bps: u64,
+) -> bool {
+    if previous_scaling_factors.is_empty()
+        || previous_scaling_factors.len() != updated_scaling_factors.len()
+    {
+        return false;
+    }
+
+    previous_scaling_factors
+        .iter()
+        .zip(updated_scaling_factors)
+        .all(|(&previous, &updated)| {
+            let change = previous.abs_diff(updated) as u128;
+            change * 10_000 < previous as u128 * deadband_bps as u128
+        })
+}
+
+/// Limits each of the updated scaling factors so that it moves at most `max_change_bps`
+/// relative to the previously applied scaling factor
+///
+/// Ex: If the previous scaling factors were [100000, 100000], the updated factors
+///     are [100000, 150000], and the max change is 1000 (10%), the scaling factors
+///     would be clamped to [100000, 110000]
+pub fn clamp_scaling_factors(
+    previous_scaling_factors: &[u64],
+    updated_scaling_factors: &[u64],
+    max_change_bps: u64,
+) -> Vec<u64> {
+    if previous_scaling_factors.len() != updated_scaling_factors.len() {
+        return updated_scaling_factors.to_vec();
+    }
+
+    previous_scaling_factors
+        .iter()
+        .zip(updated_scaling_factors)
+        .map(|(&previous, &updated)| {
+            let max_change = u64::try_from(previous as u128 * max_change_bps as u128 / 10_000)
+                .unwrap_or(u64::MAX);
+            if updated > previous {
+                updated.min(previous.saturating_add(max_change))
+            } else {
+                updated.max(previous.saturating_sub(max_change))
+            }
+        })
+        .collect()
+}
+
+/// Applies a signed offset (in basis points) to the redemption rate, where a positive
+/// offset is a premium and a negative offset is a discount
+///
+/// Ex: An offset of 50 (0.5%) takes a redemption rate of 1.2 to 1.206,
+///     and an offset of -50 takes it to 1.194
+pub fn apply_redemption_rate_offset(redemption_rate: Decimal, offset_bps: i64) -> Decimal {
+    let offset = redemption_rate * Decimal::from_ratio(offset_bps.unsigned_abs(), 10_000u64);
+    if offset_bps >= 0 {
+        redemption_rate + offset
+    } else {
+        redemption_rate.saturating_sub(offset)
+    }
+}
+
+/// Validates that the config's max redemption rate offset is within the protocol limit
+pub fn validate_max_redemption_rate_offset(max_offset_bps: u64) -> Result<(), ContractError> {
+    if max_offset_bps > MAX_REDEMPTION_RATE_OFFSET_LIMIT_BPS {
+        return Err(ContractError::InvalidMaxRedemptionRateOffset {
+            max_offset_bps,
+            max_offset_limit_bps: MAX_REDEMPTION_RATE_OFFSET_LIMIT_BPS,
+        });
+    }
+    Ok(())
+}
 
-    match asset_ordering {
-        AssetOrdering::StTokenFirst => vec![multiplier_int, scaling_factor],
-        AssetOrdering::NativeTokenFirst => vec![scaling_factor, multiplier_int],
+/// Validates that the config's override delay is at least the minimum override delay
+pub fn validate_override_delay(override_delay_seconds: u64) -> Result<(), ContractError> {
+    if override_delay_seconds < MIN_OVERRIDE_DELAY_SECONDS {
+        return Err(ContractError::InvalidOverrideDelay {
+            override_delay_seconds,
+            min_override_delay_seconds: MIN_OVERRIDE_DELAY_SECONDS,
+        });
     }
+    Ok(())
+}
+
+/// Validates that a pool's redemption rate offset (in either direction) is within the
+/// config's max offset
+pub fn validate_redemption_rate_offset(
+    offset_bps: i64,
+    max_offset_bps: u64,
+) -> Result<(), ContractError> {
+    if offset_bps.unsigned_abs() > max_offset_bps {
+        return Err(ContractError::RedemptionRateOffsetExceedsMax {
+            offset_bps,
+            max_offset_bps,
+        });
+    }
+    Ok(())
+}
+
+/// Appends a redemption rate to the history (ordered from oldest to newest), dropping the
+/// oldest redemption rates once the history exceeds its capacity
+/// The redemption rate is ignored if it is not newer than the latest redemption rate in the
+/// history (e.g. if the oracle has not been updated since the last observation)
+pub fn record_redemption_rate_sample(
+    samples: &mut Vec<RedemptionRateSample>,
+    sample: RedemptionRateSample,
+    capacity: usize,
+) {
+    if let Some(latest) = samples.last() {
+        if sample.update_time <= latest.update_time {
+            return;
+        }
+    }
+    samples.push(sample);
+    if samples.len() > capacity {
+        samples.drain(..samples.len() - capacity);
+    }
+}
+
+/// Computes the time-weighted average redemption rate between the start and end time, where
+/// each redemption rate applies from its update time until the update time of the next
+/// redemption rate (or the end time)
+/// Time before the first redemption rate is not weighted, and if none of the redemption rates
+/// fall within the window, the latest redemption rate is returned
+/// Returns None if the history is empty
+///
+/// Ex: With redemption rates of 1.2 at t=0 and 1.3 at t=1800, the average between
+///     t=0 and t=3600 is 1.25
+pub fn time_weighted_average_redemption_rate(
+    samples: &[RedemptionRateSample],
+    start_time: u64,
+    end_time: u64,
+) -> Option<Decimal> {
+    let latest = samples.last()?;
+
+    let mut weighted_sum = Decimal::zero();
+    let mut total_weight = 0u64;
+    for (i, sample) in samples.iter().enumerate() {
+        let period_end = samples
+            .get(i + 1)
+            .map(|next| next.update_time)
+            .unwrap_or(end_time)
+            .min(end_time);
+        let period_start = sample.update_time.max(start_time);
+        if period_end <= period_start {
+            continue;
+        }
+
+        let weight = period_end - period_start;
+        weighted_sum += sample.redemption_rate * Decimal::from_ratio(weight, 1u64);
+        total_weight += weight;
+    }
+
+    if total_weight == 0 {
+        return Some(latest.redemption_rate);
+    }
+    Some(weighted_sum / Decimal::from_ratio(total_weight, 1u64))
+}
+
+/// Returns the median of the redemption rates, where the median of an even number of
+/// redemption rates is the average of the middle two
+/// Returns None if there are no redemption rates
+///
+/// Ex: The median of [1.3, 1.1, 1.2] is 1.2, and the median of [1.1, 1.2, 1.3, 1.5] is 1.25
+pub fn median_redemption_rate(redemption_rates: &[Decimal]) -> Option<Decimal> {
+    let mut redemption_rates = redemption_rates.to_vec();
+    redemption_rates.sort();
+
+    let middle = redemption_rates.len() / 2;
+    match redemption_rates.len() {
+        0 => None,
+        length if length % 2 == 1 => Some(redemption_rates[middle]),
+        _ => Some(
+            (redemption_rates[middle - 1] + redemption_rates[middle])
+                / Decimal::from_ratio(2u64, 1u64),
+        ),
+    }
+}
+
+/// Converts a price from a Pyth price feed into a redemption rate, where the price's
+/// publish time is used as the update time
+/// Returns None if the price is not positive (after truncating to 18 decimals) or overflows,
+/// or if the price's confidence interval is wider than the max (in basis points of the price)
+///
+/// Ex: A price of 120000000 with an exponent of -8 is a redemption rate of 1.2
+///     With a max confidence of 50 basis points, the confidence interval can be at most 600000
+pub fn convert_pyth_price_to_redemption_rate(
+    price: &PythPrice,
+    max_confidence_bps: u64,
+) -> Option<RedemptionRateResponse> {
+    let value: u128 = price.price.parse::<i64>().ok()?.try_into().ok()?;
+    let conf: u128 = price.conf.parse::<u64>().ok()?.into();
+    if conf.checked_mul(10_000)? > value.checked_mul(max_confidence_bps.into())? {
+        return None;
+    }
+    let redemption_rate = if price.expo < 0 {
+        Decimal::from_atomics(value, price.expo.unsigned_abs()).ok()?
+    } else {
+        let value = value.checked_mul(10u128.checked_pow(price.expo.unsigned_abs())?)?;
+        Decimal::from_atomics(value, 0).ok()?
+    };
+    if redemption_rate.is_zero() {
+        return None;
+    }
+
+    Some(RedemptionRateResponse {
+        redemption_rate,
+        update_time: price.publish_time.try_into().ok()?,
+    })
+}
+
+/// Checks whether the decrease from the previous redemption rate to the current redemption rate
+/// is larger than the tolerance (in basis points)
+/// Increases never exceed the tolerance
+///
+/// Ex: With a tolerance of 100 (1%), a decrease from 1.2 to 1.19 is allowed,
+///     but a decrease from 1.2 to 1.18 is not
+pub fn exceeds_redemption_rate_decrease_tolerance(
+    previous_redemption_rate: Decimal,
+    redemption_rate: Decimal,
+    tolerance_bps: u64,
+) -> bool {
+    if redemption_rate >= previous_redemption_rate {
+        return false;
+    }
+    let max_decrease = previous_redemption_rate * Decimal::from_ratio(tolerance_bps, 10_000u64);
+    previous_redemption_rate - redemption_rate > max_decrease
 }
 
 /// Validates the the specified pool configuration matches the actual pool returned from the query
-/// (specifically with respect to the ordering of assets)
+/// and returns the scaling factor descriptor of each asset in the pool
+///
+/// For two-asset pools, the descriptors can be derived from the location of the stToken in the
+/// pool's liquidity, and if an ordering was specified, it must match the derived ordering
+/// For pools with more than two assets, the descriptors must be specified, and the stToken
+/// must be the only oracle-driven asset
+///
+/// The contract must also be the pool's scaling factor controller, otherwise it would not be
+/// able to adjust the scaling factors
 pub fn validate_pool_configuration(
     stableswap_pool: StableswapPool,
     pool_id: u64,
     sttoken_denom: String,
-    asset_ordering: AssetOrdering,
-) -> Result<(), ContractError> {
-    // Confirm the pool ID matches and there are only two assets in the pool
+    asset_ordering: Option<AssetOrdering>,
+    asset_scaling_factors: Option<Vec<AssetScalingFactor>>,
+    contract_address: &str,
+) -> Result<Vec<AssetScalingFactor>, ContractError> {
+    // Confirm the pool ID matches and there are at least two assets in the pool
     if pool_id != stableswap_pool.id {
         return Err(ContractError::PoolNotFoundOsmosis { pool_id });
     }
-    if stableswap_pool.pool_liquidity.len() != 2 {
+    let number_of_assets = stableswap_pool.pool_liquidity.len();
+    if number_of_assets < 2 {
         return Err(ContractError::InvalidNumberOfPoolAssets {
-            number: stableswap_pool.pool_liquidity.len() as u64,
+            number: number_of_assets as u64,
         });
     }
 
-    // Confirm the ordering of stToken and native token assets matches
-    let expected_sttoken_index: usize = match asset_ordering {
-        AssetOrdering::StTokenFirst => 0,
-        _ => 1,
-    };
-    if sttoken_denom != stableswap_pool.pool_liquidity[expected_sttoken_index].denom {
-        return Err(ContractError::InvalidPoolAssetOrdering {});
+    // Confirm the contract is permitted to adjust the pool's scaling factors
+    if stableswap_pool.scaling_factor_controller != contract_address {
+        return Err(ContractError::NotScalingFactorController {
+            pool_id,
+            scaling_factor_controller: stableswap_pool.scaling_factor_controller,
+        });
     }
 
-    Ok(())
+    // Locate the stToken in the pool to determine the ordering of assets
+    let sttoken_index = stableswap_pool
+        .pool_liquidity
+        .iter()
+        .position(|coin| coin.denom == sttoken_denom)
+        .ok_or(ContractError::DenomNotInPool {
+            pool_id,
+            denom: sttoken_denom,
+        })?;
+
+    let asset_scaling_factors = match (asset_scaling_factors, asset_ordering) {
+        (Some(_), Some(_)) => {
+            return Err(ContractError::AssetOrderingAndScalingFactorsSpecified {})
+        }
+
+        // If the descriptors were specified, confirm there's one for each asset, that
+        // the stToken is the only oracle-driven asset, and that each paired stToken
+        // references the denom of the asset at the same index
+        (Some(asset_scaling_factors), None) => {
+            if asset_scaling_factors.len() != number_of_assets {
+                return Err(ContractError::InvalidNumberOfAssetScalingFactors {
+                    number: asset_scaling_factors.len() as u64,
+                    expected: number_of_assets as u64,
+                });
+            }
+            if asset_scaling_factors[sttoken_index] != (AssetScalingFactor::OracleDriven {}) {
+                return Err(ContractError::InvalidPoolAssetOrdering {});
+            }
+            for (index, asset) in asset_scaling_factors.iter().enumerate() {
+                let invalid = match asset {
+                    AssetScalingFactor::OracleDriven {} => index != sttoken_index,
+                    AssetScalingFactor::NativeAnchor {} => false,
+                    AssetScalingFactor::Fixed { scaling_factor } => *scaling_factor == 0,
+                    AssetScalingFactor::PairedStToken { denom, .. } => {
+                        *denom != stableswap_pool.pool_liquidity[index].denom
+                    }
+                };
+                if invalid {
+                    return Err(ContractError::InvalidAssetScalingFactor {
+                        index: index as u64,
+                    });
+                }
+            }
+            asset_scaling_factors
+        }
+
+        // Otherwise, derive the ordering of the stToken and native token from a two-asset pool,
+        // and if an ordering was specified, confirm it matches the actual pool
+        (None, asset_ordering) => {
+            if number_of_assets != 2 {
+                return Err(ContractError::InvalidNumberOfPoolAssets {
+                    number: number_of_assets as u64,
+                });
+            }
+            let derived_ordering = match sttoken_index {
+                0 => AssetOrdering::StTokenFirst,
+                _ => AssetOrdering::NativeTokenFirst,
+            };
+            if let Some(asset_ordering) = asset_ordering {
+                if asset_ordering != derived_ordering {
+                    return Err(ContractError::InvalidPoolAssetOrdering {});
+                }
+            }
+            derived_ordering.asset_scaling_factors()
+        }
+    };
+
+    Ok(asset_scaling_factors)
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
     use std::str::FromStr;
     use std::vec;
 
@@ -72,14 +605,33 @@ mod tests {
     use osmosis_std::types::osmosis::gamm::poolmodels::stableswap::v1beta1::Pool as StableswapPool;
 
     use crate::{
-        helpers::convert_redemption_rate_to_scaling_factors, state::AssetOrdering, ContractError,
+        helpers::convert_redemption_rate_to_scaling_factors,
+        msg::{PythPrice, RedemptionRateResponse},
+        state::{
+            AssetOrdering, AssetScalingFactor, RedemptionRateSample, RoundingMode,
+            ScalingFactorRamp,
+        },
+        ContractError,
     };
 
-    use super::validate_pool_configuration;
+    use super::{
+        apply_redemption_rate_offset, clamp_scaling_factors, convert_pyth_price_to_redemption_rate,
+        exceeds_max_scaling_factor_change, exceeds_redemption_rate_decrease_tolerance,
+        format_asset_scaling_factors, format_scaling_factors, interpolate_scaling_factors,
+        median_redemption_rate, normalize_scaling_factors_for_decimals,
+        record_redemption_rate_sample, time_weighted_average_redemption_rate,
+        validate_asset_decimals, validate_max_redemption_rate_offset, validate_pool_configuration,
+        validate_redemption_rate_offset, validate_scaling_factor_multiplier, within_deadband,
+        DEFAULT_SCALING_FACTOR_MULTIPLIER, MAX_REDEMPTION_RATE_OFFSET_LIMIT_BPS,
+        MAX_SUPPORTED_REDEMPTION_RATE,
+    };
+
+    const TEST_CONTRACT_ADDRESS: &str = "contract";
 
     // Helper function to build a stableswap pool from an array of denoms
     // E.g. ["sttoken", "native_token"], builds a pool with liquidity
     //      [Coin{"sttoken", 100000}, Coin{"native_token", 100000}]
+    // The contract is set as the pool's scaling factor controller
     fn get_test_stableswap_pool(pool_id: u64, liquidity_denoms: Vec<&str>) -> StableswapPool {
         let pool_liquidity = liquidity_denoms
             .into_iter()
@@ -92,6 +644,7 @@ mod tests {
         StableswapPool {
             id: pool_id,
             pool_liquidity,
+            scaling_factor_controller: TEST_CONTRACT_ADDRESS.to_string(),
             ..Default::default()
         }
     }
@@ -101,8 +654,14 @@ mod tests {
         let redemption_rate = Decimal::from_str("1.0").unwrap();
         let asset_ordering = AssetOrdering::StTokenFirst;
         assert_eq!(
-            convert_redemption_rate_to_scaling_factors(redemption_rate, asset_ordering),
-            vec![100000, 100000],
+            convert_redemption_rate_to_scaling_factors(
+                redemption_rate,
+                &asset_ordering.asset_scaling_factors(),
+                &HashMap::new(),
+                DEFAULT_SCALING_FACTOR_MULTIPLIER,
+                &RoundingMode::Floor
+            ),
+            Ok(vec![100000, 100000]),
         );
     }
 
@@ -111,8 +670,14 @@ mod tests {
         let redemption_rate = Decimal::from_str("1.2").unwrap();
         let asset_ordering = AssetOrdering::NativeTokenFirst;
         assert_eq!(
-            convert_redemption_rate_to_scaling_factors(redemption_rate, asset_ordering),
-            vec![120000, 100000],
+            convert_redemption_rate_to_scaling_factors(
+                redemption_rate,
+                &asset_ordering.asset_scaling_factors(),
+                &HashMap::new(),
+                DEFAULT_SCALING_FACTOR_MULTIPLIER,
+                &RoundingMode::Floor
+            ),
+            Ok(vec![120000, 100000]),
         );
     }
 
@@ -121,8 +686,14 @@ mod tests {
         let redemption_rate = Decimal::from_str("1.25").unwrap();
         let asset_ordering = AssetOrdering::StTokenFirst;
         assert_eq!(
-            convert_redemption_rate_to_scaling_factors(redemption_rate, asset_ordering),
-            vec![100000, 125000],
+            convert_redemption_rate_to_scaling_factors(
+                redemption_rate,
+                &asset_ordering.asset_scaling_factors(),
+                &HashMap::new(),
+                DEFAULT_SCALING_FACTOR_MULTIPLIER,
+                &RoundingMode::Floor
+            ),
+            Ok(vec![100000, 125000]),
         );
     }
 
@@ -131,8 +702,14 @@ mod tests {
         let redemption_rate = Decimal::from_str("1.25236").unwrap();
         let asset_ordering = AssetOrdering::NativeTokenFirst;
         assert_eq!(
-            convert_redemption_rate_to_scaling_factors(redemption_rate, asset_ordering),
-            vec![125236, 100000],
+            convert_redemption_rate_to_scaling_factors(
+                redemption_rate,
+                &asset_ordering.asset_scaling_factors(),
+                &HashMap::new(),
+                DEFAULT_SCALING_FACTOR_MULTIPLIER,
+                &RoundingMode::Floor
+            ),
+            Ok(vec![125236, 100000]),
         );
     }
 
@@ -140,10 +717,60 @@ mod tests {
     fn test_convert_to_scaling_factor_decimal_truncation() {
         let redemption_rate = Decimal::from_str("1.252369923948298234").unwrap();
         let asset_ordering = AssetOrdering::StTokenFirst;
-        assert_eq!(
-            convert_redemption_rate_to_scaling_factors(redemption_rate, asset_ordering),
-            vec![100000, 125236],
-        );
+        for (rounding_mode, expected_scaling_factors) in [
+            (RoundingMode::Floor, vec![100000, 125236]),
+            (RoundingMode::Ceil, vec![100000, 125237]),
+            (RoundingMode::HalfEven, vec![100000, 125237]),
+        ] {
+            assert_eq!(
+                convert_redemption_rate_to_scaling_factors(
+                    redemption_rate,
+                    &asset_ordering.asset_scaling_factors(),
+                    &HashMap::new(),
+                    DEFAULT_SCALING_FACTOR_MULTIPLIER,
+                    &rounding_mode
+                ),
+                Ok(expected_scaling_factors),
+                "rounding mode: {}",
+                rounding_mode
+            );
+        }
+    }
+
+    #[test]
+    fn test_convert_to_scaling_factor_rounding_ties() {
+        let asset_ordering = AssetOrdering::NativeTokenFirst;
+        for (redemption_rate, rounding_mode, expected_scaling_factor) in [
+            // Exact values are unaffected by the rounding mode
+            ("1.25236", RoundingMode::Floor, 125236),
+            ("1.25236", RoundingMode::Ceil, 125236),
+            ("1.25236", RoundingMode::HalfEven, 125236),
+            // Below the midpoint
+            ("1.252364", RoundingMode::Floor, 125236),
+            ("1.252364", RoundingMode::Ceil, 125237),
+            ("1.252364", RoundingMode::HalfEven, 125236),
+            // Ties round to the even neighbor
+            ("1.252365", RoundingMode::Floor, 125236),
+            ("1.252365", RoundingMode::Ceil, 125237),
+            ("1.252365", RoundingMode::HalfEven, 125236),
+            ("1.252375", RoundingMode::HalfEven, 125238),
+            // Above the midpoint
+            ("1.2523651", RoundingMode::HalfEven, 125237),
+        ] {
+            assert_eq!(
+                convert_redemption_rate_to_scaling_factors(
+                    Decimal::from_str(redemption_rate).unwrap(),
+                    &asset_ordering.asset_scaling_factors(),
+                    &HashMap::new(),
+                    DEFAULT_SCALING_FACTOR_MULTIPLIER,
+                    &rounding_mode
+                ),
+                Ok(vec![expected_scaling_factor, 100000]),
+                "redemption rate: {}, rounding mode: {}",
+                redemption_rate,
+                rounding_mode
+            );
+        }
     }
 
     #[test]
@@ -151,8 +778,14 @@ mod tests {
         let redemption_rate = Decimal::from_str("0.9837").unwrap();
         let asset_ordering = AssetOrdering::NativeTokenFirst;
         assert_eq!(
-            convert_redemption_rate_to_scaling_factors(redemption_rate, asset_ordering),
-            vec![98370, 100000],
+            convert_redemption_rate_to_scaling_factors(
+                redemption_rate,
+                &asset_ordering.asset_scaling_factors(),
+                &HashMap::new(),
+                DEFAULT_SCALING_FACTOR_MULTIPLIER,
+                &RoundingMode::Floor
+            ),
+            Ok(vec![98370, 100000]),
         );
     }
 
@@ -161,8 +794,628 @@ mod tests {
         let redemption_rate = Decimal::from_str("0.0").unwrap();
         let asset_ordering = AssetOrdering::StTokenFirst;
         assert_eq!(
-            convert_redemption_rate_to_scaling_factors(redemption_rate, asset_ordering),
-            vec![100000, 0],
+            convert_redemption_rate_to_scaling_factors(
+                redemption_rate,
+                &asset_ordering.asset_scaling_factors(),
+                &HashMap::new(),
+                DEFAULT_SCALING_FACTOR_MULTIPLIER,
+                &RoundingMode::Floor
+            ),
+            Ok(vec![100000, 0]),
+        );
+    }
+
+    #[test]
+    fn test_convert_to_scaling_factor_multi_asset() {
+        let redemption_rate = Decimal::from_str("1.2").unwrap();
+        let asset_scaling_factors = vec![
+            AssetScalingFactor::NativeAnchor {},
+            AssetScalingFactor::OracleDriven {},
+            AssetScalingFactor::Fixed {
+                scaling_factor: 100000,
+            },
+        ];
+        assert_eq!(
+            convert_redemption_rate_to_scaling_factors(
+                redemption_rate,
+                &asset_scaling_factors,
+                &HashMap::new(),
+                DEFAULT_SCALING_FACTOR_MULTIPLIER,
+                &RoundingMode::Floor
+            ),
+            Ok(vec![120000, 100000, 100000]),
+        );
+    }
+
+    #[test]
+    fn test_convert_to_scaling_factor_paired_sttoken() {
+        let redemption_rate = Decimal::from_str("1.2").unwrap();
+        let asset_scaling_factors = vec![
+            AssetScalingFactor::OracleDriven {},
+            AssetScalingFactor::PairedStToken {
+                denom: "stkatom".to_string(),
+                oracle_contract_address: None,
+            },
+        ];
+
+        let paired_redemption_rates =
+            HashMap::from([("stkatom".to_string(), Decimal::from_str("1.1").unwrap())]);
+        assert_eq!(
+            convert_redemption_rate_to_scaling_factors(
+                redemption_rate,
+                &asset_scaling_factors,
+                &paired_redemption_rates,
+                DEFAULT_SCALING_FACTOR_MULTIPLIER,
+                &RoundingMode::Floor
+            ),
+            Ok(vec![100000, 109090]),
+        );
+
+        // The paired redemption rate must be provided and non-zero
+        let invalid_redemption_rate = Err(ContractError::InvalidRedemptionRate {
+            token: "stkatom".to_string(),
+        });
+        assert_eq!(
+            convert_redemption_rate_to_scaling_factors(
+                redemption_rate,
+                &asset_scaling_factors,
+                &HashMap::new(),
+                DEFAULT_SCALING_FACTOR_MULTIPLIER,
+                &RoundingMode::Floor
+            ),
+            invalid_redemption_rate
+        );
+        assert_eq!(
+            convert_redemption_rate_to_scaling_factors(
+                redemption_rate,
+                &asset_scaling_factors,
+                &HashMap::from([("stkatom".to_string(), Decimal::zero())]),
+                DEFAULT_SCALING_FACTOR_MULTIPLIER,
+                &RoundingMode::Floor
+            ),
+            invalid_redemption_rate
+        );
+    }
+
+    #[test]
+    fn test_convert_to_scaling_factor_multiplier() {
+        let redemption_rate = Decimal::from_str("1.252369923948298234").unwrap();
+        let asset_scaling_factors = AssetOrdering::StTokenFirst.asset_scaling_factors();
+
+        let convert = |multiplier| {
+            convert_redemption_rate_to_scaling_factors(
+                redemption_rate,
+                &asset_scaling_factors,
+                &HashMap::new(),
+                multiplier,
+                &RoundingMode::Floor,
+            )
+        };
+        assert_eq!(convert(1_000_000), Ok(vec![1_000_000, 1_252_369]));
+        assert_eq!(convert(100_000_000), Ok(vec![100_000_000, 125_236_992]));
+
+        // Scaling factors that don't fit in a u64 should error rather than truncate
+        assert_eq!(
+            convert(u64::MAX),
+            Err(ContractError::ScalingFactorOverflow {})
+        );
+    }
+
+    #[test]
+    fn test_validate_scaling_factor_multiplier() {
+        let max_multiplier = u64::MAX / MAX_SUPPORTED_REDEMPTION_RATE;
+
+        assert_eq!(validate_scaling_factor_multiplier(100_000), Ok(()));
+        assert_eq!(validate_scaling_factor_multiplier(max_multiplier), Ok(()));
+
+        for multiplier in [0, max_multiplier + 1] {
+            assert_eq!(
+                validate_scaling_factor_multiplier(multiplier),
+                Err(ContractError::InvalidScalingFactorMultiplier {
+                    multiplier,
+                    max_multiplier
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn test_normalize_scaling_factors_for_decimals() {
+        // Assets with the same decimals are unchanged
+        assert_eq!(
+            normalize_scaling_factors_for_decimals(vec![100000, 120000], &[6, 6]),
+            Ok(vec![100000, 120000])
+        );
+
+        // Assets with more decimals are scaled up by the difference
+        assert_eq!(
+            normalize_scaling_factors_for_decimals(vec![100000, 120000], &[6, 18]),
+            Ok(vec![100000, 120000000000000000])
+        );
+        assert_eq!(
+            normalize_scaling_factors_for_decimals(vec![120000, 100000, 100000], &[8, 6, 6]),
+            Ok(vec![12000000, 100000, 100000])
+        );
+
+        // Scaling factors that overflow should error
+        assert_eq!(
+            normalize_scaling_factors_for_decimals(vec![100000, 1_000_000_000], &[0, 18]),
+            Err(ContractError::ScalingFactorOverflow {})
+        );
+    }
+
+    #[test]
+    fn test_validate_asset_decimals() {
+        let multiplier = DEFAULT_SCALING_FACTOR_MULTIPLIER;
+
+        assert_eq!(validate_asset_decimals(&[6, 6], 2, multiplier), Ok(()));
+        assert_eq!(validate_asset_decimals(&[6, 18], 2, multiplier), Ok(()));
+
+        // There must be decimals for each asset
+        assert_eq!(
+            validate_asset_decimals(&[6, 18], 3, multiplier),
+            Err(ContractError::InvalidNumberOfAssetDecimals {
+                number: 2,
+                expected: 3
+            })
+        );
+
+        // The difference in decimals cannot overflow the scaling factors
+        assert_eq!(
+            validate_asset_decimals(&[0, 18], 2, multiplier),
+            Err(ContractError::InvalidAssetDecimals {
+                decimal_difference: 18
+            })
+        );
+        assert_eq!(
+            validate_asset_decimals(&[6, 18], 2, 100_000_000),
+            Err(ContractError::InvalidAssetDecimals {
+                decimal_difference: 12
+            })
+        );
+    }
+
+    #[test]
+    fn test_interpolate_scaling_factors() {
+        let ramp = ScalingFactorRamp {
+            initial_scaling_factors: vec![100000, 120000, 150000],
+            target_scaling_factors: vec![100000, 140000, 110000],
+            start_time: 1000,
+            end_time: 2000,
+        };
+
+        // Before or at the start, the scaling factors are unchanged
+        assert_eq!(
+            interpolate_scaling_factors(&ramp, 500),
+            vec![100000, 120000, 150000]
+        );
+        assert_eq!(
+            interpolate_scaling_factors(&ramp, 1000),
+            vec![100000, 120000, 150000]
+        );
+
+        // Part of the way through, each scaling factor moves proportionally toward the target
+        assert_eq!(
+            interpolate_scaling_factors(&ramp, 1250),
+            vec![100000, 125000, 140000]
+        );
+        assert_eq!(
+            interpolate_scaling_factors(&ramp, 1500),
+            vec![100000, 130000, 130000]
+        );
+
+        // At or after the end, the scaling factors are at the target
+        assert_eq!(
+            interpolate_scaling_factors(&ramp, 2000),
+            vec![100000, 140000, 110000]
+        );
+        assert_eq!(
+            interpolate_scaling_factors(&ramp, 5000),
+            vec![100000, 140000, 110000]
+        );
+
+        // A ramp without a duration goes straight to the target
+        let ramp = ScalingFactorRamp {
+            end_time: 1000,
+            ..ramp
+        };
+        assert_eq!(
+            interpolate_scaling_factors(&ramp, 1000),
+            vec![100000, 140000, 110000]
+        );
+    }
+
+    #[test]
+    fn test_within_deadband() {
+        // 10 bps deadband, changes under 0.1% are within the deadband
+        assert!(within_deadband(&[100000, 120000], &[100000, 120000], 10));
+        assert!(within_deadband(&[100000, 120000], &[100000, 120119], 10));
+        assert!(within_deadband(&[100000, 120000], &[100000, 119881], 10));
+
+        // A change of exactly the deadband or more is not within the deadband
+        assert!(!within_deadband(&[100000, 120000], &[100000, 120120], 10));
+        assert!(!within_deadband(&[100000, 120000], &[100000, 125000], 10));
+        assert!(!within_deadband(&[100000, 120000], &[100200, 120000], 10));
+
+        // Without previous scaling factors, the update is never within the deadband
+        assert!(!within_deadband(&[], &[100000, 120000], 10));
+        assert!(!within_deadband(&[100000], &[100000, 120000], 10));
+
+        // A zero deadband never skips an update
+        assert!(!within_deadband(&[100000, 120000], &[100000, 120000], 0));
+    }
+
+    #[test]
+    fn test_format_asset_scaling_factors() {
+        assert_eq!(
+            format_asset_scaling_factors(&[
+                AssetScalingFactor::OracleDriven {},
+                AssetScalingFactor::NativeAnchor {},
+                AssetScalingFactor::Fixed {
+                    scaling_factor: 100000
+                },
+            ]),
+            "[oracle_driven, native_anchor, fixed(100000)]"
+        );
+    }
+
+    #[test]
+    fn test_format_scaling_factors() {
+        assert_eq!(
+            format_scaling_factors(&[100000, 120000]),
+            "[100000, 120000]"
+        );
+        assert_eq!(format_scaling_factors(&[1]), "[1]");
+        assert_eq!(format_scaling_factors(&[]), "[]");
+    }
+
+    #[test]
+    fn test_exceeds_max_scaling_factor_change() {
+        let previous = vec![100000, 120000];
+        let max_change_bps = 500; // 5%
+
+        // No change
+        assert!(!exceeds_max_scaling_factor_change(
+            &previous,
+            &[100000, 120000],
+            max_change_bps
+        ));
+
+        // Increase and decrease exactly at the threshold (5% of 120000 is 6000)
+        assert!(!exceeds_max_scaling_factor_change(
+            &previous,
+            &[100000, 126000],
+            max_change_bps
+        ));
+        assert!(!exceeds_max_scaling_factor_change(
+            &previous,
+            &[100000, 114000],
+            max_change_bps
+        ));
+
+        // Increase and decrease just past the threshold
+        assert!(exceeds_max_scaling_factor_change(
+            &previous,
+            &[100000, 126001],
+            max_change_bps
+        ));
+        assert!(exceeds_max_scaling_factor_change(
+            &previous,
+            &[100000, 113999],
+            max_change_bps
+        ));
+
+        // No previous scaling factors (i.e. first update)
+        assert!(!exceeds_max_scaling_factor_change(
+            &[],
+            &[100000, 200000],
+            max_change_bps
+        ));
+    }
+
+    #[test]
+    fn test_clamp_scaling_factors() {
+        let previous = vec![100000, 120000];
+        let max_change_bps = 1000; // 10%
+
+        // Within the max change - no clamping
+        assert_eq!(
+            clamp_scaling_factors(&previous, &[100000, 125000], max_change_bps),
+            vec![100000, 125000]
+        );
+
+        // Increase past the max change
+        assert_eq!(
+            clamp_scaling_factors(&previous, &[100000, 180000], max_change_bps),
+            vec![100000, 132000]
+        );
+
+        // Decrease past the max change
+        assert_eq!(
+            clamp_scaling_factors(&previous, &[100000, 60000], max_change_bps),
+            vec![100000, 108000]
+        );
+
+        // No previous scaling factors (i.e. first update)
+        assert_eq!(
+            clamp_scaling_factors(&[], &[100000, 180000], max_change_bps),
+            vec![100000, 180000]
+        );
+    }
+
+    #[test]
+    fn test_exceeds_redemption_rate_decrease_tolerance() {
+        let previous = Decimal::from_str("1.2").unwrap();
+        let tolerance_bps = 100; // 1%
+
+        // Increases and unchanged rates never exceed the tolerance
+        let increase = Decimal::from_str("1.3").unwrap();
+        assert!(!exceeds_redemption_rate_decrease_tolerance(
+            previous,
+            increase,
+            tolerance_bps
+        ));
+        assert!(!exceeds_redemption_rate_decrease_tolerance(
+            previous,
+            previous,
+            tolerance_bps
+        ));
+
+        // Decrease exactly at the tolerance (1% of 1.2 is 0.012)
+        let decrease = Decimal::from_str("1.188").unwrap();
+        assert!(!exceeds_redemption_rate_decrease_tolerance(
+            previous,
+            decrease,
+            tolerance_bps
+        ));
+
+        // Decrease just past the tolerance
+        let decrease = Decimal::from_str("1.187999").unwrap();
+        assert!(exceeds_redemption_rate_decrease_tolerance(
+            previous,
+            decrease,
+            tolerance_bps
+        ));
+
+        // With a tolerance of zero, any decrease exceeds the tolerance
+        let decrease = Decimal::from_str("1.199999").unwrap();
+        assert!(exceeds_redemption_rate_decrease_tolerance(
+            previous, decrease, 0
+        ));
+    }
+
+    #[test]
+    fn test_apply_redemption_rate_offset() {
+        let redemption_rate = Decimal::from_str("1.2").unwrap();
+
+        // No offset
+        assert_eq!(
+            apply_redemption_rate_offset(redemption_rate, 0),
+            redemption_rate
+        );
+
+        // Premium of 0.5%
+        assert_eq!(
+            apply_redemption_rate_offset(redemption_rate, 50),
+            Decimal::from_str("1.206").unwrap()
+        );
+
+        // Discount of 0.5%
+        assert_eq!(
+            apply_redemption_rate_offset(redemption_rate, -50),
+            Decimal::from_str("1.194").unwrap()
+        );
+
+        // A discount of 100% or more floors the redemption rate at zero
+        assert_eq!(
+            apply_redemption_rate_offset(redemption_rate, -20_000),
+            Decimal::zero()
+        );
+    }
+
+    #[test]
+    fn test_validate_redemption_rate_offset() {
+        // The max offset must be within the protocol limit
+        assert_eq!(validate_max_redemption_rate_offset(0), Ok(()));
+        assert_eq!(
+            validate_max_redemption_rate_offset(MAX_REDEMPTION_RATE_OFFSET_LIMIT_BPS),
+            Ok(())
+        );
+        assert_eq!(
+            validate_max_redemption_rate_offset(MAX_REDEMPTION_RATE_OFFSET_LIMIT_BPS + 1),
+            Err(ContractError::InvalidMaxRedemptionRateOffset {
+                max_offset_bps: MAX_REDEMPTION_RATE_OFFSET_LIMIT_BPS + 1,
+                max_offset_limit_bps: MAX_REDEMPTION_RATE_OFFSET_LIMIT_BPS,
+            })
+        );
+
+        // Offsets in either direction are bounded by the max
+        assert_eq!(validate_redemption_rate_offset(100, 100), Ok(()));
+        assert_eq!(validate_redemption_rate_offset(-100, 100), Ok(()));
+        assert_eq!(
+            validate_redemption_rate_offset(101, 100),
+            Err(ContractError::RedemptionRateOffsetExceedsMax {
+                offset_bps: 101,
+                max_offset_bps: 100
+            })
+        );
+        assert_eq!(
+            validate_redemption_rate_offset(-101, 100),
+            Err(ContractError::RedemptionRateOffsetExceedsMax {
+                offset_bps: -101,
+                max_offset_bps: 100
+            })
+        );
+    }
+
+    fn get_sample(update_time: u64, redemption_rate: &str) -> RedemptionRateSample {
+        RedemptionRateSample {
+            update_time,
+            redemption_rate: Decimal::from_str(redemption_rate).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_record_redemption_rate_sample() {
+        let mut samples = vec![];
+
+        // Samples are appended in order
+        record_redemption_rate_sample(&mut samples, get_sample(10, "1.1"), 3);
+        record_redemption_rate_sample(&mut samples, get_sample(20, "1.2"), 3);
+        assert_eq!(samples, vec![get_sample(10, "1.1"), get_sample(20, "1.2")]);
+
+        // A sample that's not newer than the latest sample is ignored
+        record_redemption_rate_sample(&mut samples, get_sample(20, "1.3"), 3);
+        record_redemption_rate_sample(&mut samples, get_sample(15, "1.3"), 3);
+        assert_eq!(samples, vec![get_sample(10, "1.1"), get_sample(20, "1.2")]);
+
+        // Once the capacity is exceeded, the oldest sample is dropped
+        record_redemption_rate_sample(&mut samples, get_sample(30, "1.3"), 3);
+        record_redemption_rate_sample(&mut samples, get_sample(40, "1.4"), 3);
+        assert_eq!(
+            samples,
+            vec![
+                get_sample(20, "1.2"),
+                get_sample(30, "1.3"),
+                get_sample(40, "1.4")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_time_weighted_average_redemption_rate() {
+        // No samples
+        assert_eq!(time_weighted_average_redemption_rate(&[], 0, 100), None);
+
+        let samples = vec![get_sample(1000, "1.2"), get_sample(2800, "1.3")];
+        let twap = |start_time, end_time| {
+            time_weighted_average_redemption_rate(&samples, start_time, end_time).unwrap()
+        };
+
+        // Each rate is weighted by the time until the next sample
+        assert_eq!(twap(1000, 4600), Decimal::from_str("1.25").unwrap());
+        assert_eq!(twap(1900, 3700), Decimal::from_str("1.25").unwrap());
+
+        // Time before the first sample is not weighted
+        assert_eq!(twap(0, 4600), Decimal::from_str("1.25").unwrap());
+
+        // Samples after the end time are ignored
+        assert_eq!(twap(0, 2800), Decimal::from_str("1.2").unwrap());
+
+        // Samples that have been fully superseded before the start time are ignored
+        assert_eq!(twap(3000, 4000), Decimal::from_str("1.3").unwrap());
+
+        // If no time has elapsed within the window, the latest rate is used
+        assert_eq!(twap(2800, 2800), Decimal::from_str("1.3").unwrap());
+        assert_eq!(twap(0, 1000), Decimal::from_str("1.3").unwrap());
+    }
+
+    #[test]
+    fn test_median_redemption_rate() {
+        let rates = |rates: &[&str]| -> Vec<Decimal> {
+            rates
+                .iter()
+                .map(|r| Decimal::from_str(r).unwrap())
+                .collect()
+        };
+
+        assert_eq!(median_redemption_rate(&[]), None);
+        assert_eq!(
+            median_redemption_rate(&rates(&["1.2"])),
+            Some(Decimal::from_str("1.2").unwrap())
+        );
+        assert_eq!(
+            median_redemption_rate(&rates(&["1.3", "1.1", "1.2"])),
+            Some(Decimal::from_str("1.2").unwrap())
+        );
+        assert_eq!(
+            median_redemption_rate(&rates(&["1.5", "1.1", "1.3", "1.2"])),
+            Some(Decimal::from_str("1.25").unwrap())
+        );
+
+        // A single outlier does not move the median
+        assert_eq!(
+            median_redemption_rate(&rates(&["1.2", "1.21", "100"])),
+            Some(Decimal::from_str("1.21").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_convert_pyth_price_to_redemption_rate() {
+        let price = |price: &str, expo: i32, publish_time: i64| PythPrice {
+            price: price.to_string(),
+            conf: "0".to_string(),
+            expo,
+            publish_time,
+        };
+
+        assert_eq!(
+            convert_pyth_price_to_redemption_rate(&price("120000000", -8, 100), 50),
+            Some(RedemptionRateResponse {
+                redemption_rate: Decimal::from_str("1.2").unwrap(),
+                update_time: 100,
+            })
+        );
+        assert_eq!(
+            convert_pyth_price_to_redemption_rate(&price("12", 0, 100), 50),
+            Some(RedemptionRateResponse {
+                redemption_rate: Decimal::from_str("12").unwrap(),
+                update_time: 100,
+            })
+        );
+        assert_eq!(
+            convert_pyth_price_to_redemption_rate(&price("12", 1, 100), 50),
+            Some(RedemptionRateResponse {
+                redemption_rate: Decimal::from_str("120").unwrap(),
+                update_time: 100,
+            })
+        );
+
+        // Non-positive and malformed prices are rejected
+        assert_eq!(
+            convert_pyth_price_to_redemption_rate(&price("0", -8, 100), 50),
+            None
+        );
+        assert_eq!(
+            convert_pyth_price_to_redemption_rate(&price("-120000000", -8, 100), 50),
+            None
+        );
+        assert_eq!(
+            convert_pyth_price_to_redemption_rate(&price("abc", -8, 100), 50),
+            None
+        );
+
+        // A price that truncates to zero is rejected
+        assert_eq!(
+            convert_pyth_price_to_redemption_rate(&price("1", -19, 100), 50),
+            None
+        );
+
+        // A negative publish time is rejected
+        assert_eq!(
+            convert_pyth_price_to_redemption_rate(&price("120000000", -8, -1), 50),
+            None
+        );
+
+        // A price with a confidence interval wider than the max is rejected
+        let price_with_conf = |conf: &str| PythPrice {
+            conf: conf.to_string(),
+            ..price("120000000", -8, 100)
+        };
+        assert_eq!(
+            convert_pyth_price_to_redemption_rate(&price_with_conf("600000"), 50),
+            Some(RedemptionRateResponse {
+                redemption_rate: Decimal::from_str("1.2").unwrap(),
+                update_time: 100,
+            })
+        );
+        assert_eq!(
+            convert_pyth_price_to_redemption_rate(&price_with_conf("600001"), 50),
+            None
+        );
+        assert_eq!(
+            convert_pyth_price_to_redemption_rate(&price_with_conf("-1"), 50),
+            None
         );
     }
 
@@ -180,9 +1433,11 @@ mod tests {
                 actual_pool,
                 pool_id,
                 sttoken_denom.to_string(),
-                asset_ordering
+                Some(asset_ordering.clone()),
+                None,
+                TEST_CONTRACT_ADDRESS
             ),
-            Ok(())
+            Ok(asset_ordering.asset_scaling_factors())
         );
     }
 
@@ -200,9 +1455,11 @@ mod tests {
                 actual_pool,
                 pool_id,
                 sttoken_denom.to_string(),
-                asset_ordering
+                Some(asset_ordering.clone()),
+                None,
+                TEST_CONTRACT_ADDRESS
             ),
-            Ok(())
+            Ok(asset_ordering.asset_scaling_factors())
         );
     }
 
@@ -222,7 +1479,9 @@ mod tests {
                 actual_pool,
                 configured_pool_id,
                 sttoken_denom.to_string(),
-                asset_ordering
+                Some(asset_ordering),
+                None,
+                TEST_CONTRACT_ADDRESS
             ),
             Err(ContractError::PoolNotFoundOsmosis {
                 pool_id: configured_pool_id
@@ -245,7 +1504,9 @@ mod tests {
                 actual_pool,
                 pool_id,
                 sttoken_denom.to_string(),
-                configured_ordering
+                Some(configured_ordering),
+                None,
+                TEST_CONTRACT_ADDRESS
             ),
             Err(ContractError::InvalidPoolAssetOrdering {})
         );
@@ -259,9 +1520,230 @@ mod tests {
                 actual_pool,
                 pool_id,
                 sttoken_denom.to_string(),
-                configured_ordering
+                Some(configured_ordering),
+                None,
+                TEST_CONTRACT_ADDRESS
             ),
             Err(ContractError::InvalidPoolAssetOrdering {})
         );
     }
+
+    #[test]
+    fn test_validate_pool_configuration_not_scaling_factor_controller() {
+        let pool_id = 2;
+        let sttoken_denom = "ibc/sttoken";
+
+        // Actual pool is controlled by a different address
+        let actual_pool = StableswapPool {
+            scaling_factor_controller: "other_controller".to_string(),
+            ..get_test_stableswap_pool(pool_id, vec![sttoken_denom, "native"])
+        };
+
+        assert_eq!(
+            validate_pool_configuration(
+                actual_pool,
+                pool_id,
+                sttoken_denom.to_string(),
+                Some(AssetOrdering::StTokenFirst),
+                None,
+                TEST_CONTRACT_ADDRESS
+            ),
+            Err(ContractError::NotScalingFactorController {
+                pool_id,
+                scaling_factor_controller: "other_controller".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_pool_configuration_multi_asset() {
+        let pool_id = 2;
+        let sttoken_denom = "ibc/sttoken";
+        let actual_pool =
+            get_test_stableswap_pool(pool_id, vec!["native", sttoken_denom, "ibc/other"]);
+
+        let valid_asset_scaling_factors = vec![
+            AssetScalingFactor::NativeAnchor {},
+            AssetScalingFactor::OracleDriven {},
+            AssetScalingFactor::Fixed {
+                scaling_factor: 100000,
+            },
+        ];
+        let validate = |asset_ordering, asset_scaling_factors| {
+            validate_pool_configuration(
+                actual_pool.clone(),
+                pool_id,
+                sttoken_denom.to_string(),
+                asset_ordering,
+                asset_scaling_factors,
+                TEST_CONTRACT_ADDRESS,
+            )
+        };
+
+        // Valid configuration
+        assert_eq!(
+            validate(None, Some(valid_asset_scaling_factors.clone())),
+            Ok(valid_asset_scaling_factors.clone())
+        );
+
+        // The descriptors are required for pools with more than two assets
+        assert_eq!(
+            validate(None, None),
+            Err(ContractError::InvalidNumberOfPoolAssets { number: 3 })
+        );
+        assert_eq!(
+            validate(Some(AssetOrdering::StTokenFirst), None),
+            Err(ContractError::InvalidNumberOfPoolAssets { number: 3 })
+        );
+
+        // The ordering and descriptors cannot both be specified
+        assert_eq!(
+            validate(
+                Some(AssetOrdering::NativeTokenFirst),
+                Some(valid_asset_scaling_factors)
+            ),
+            Err(ContractError::AssetOrderingAndScalingFactorsSpecified {})
+        );
+
+        // There must be a descriptor for each asset
+        assert_eq!(
+            validate(
+                None,
+                Some(vec![
+                    AssetScalingFactor::NativeAnchor {},
+                    AssetScalingFactor::OracleDriven {},
+                ])
+            ),
+            Err(ContractError::InvalidNumberOfAssetScalingFactors {
+                number: 2,
+                expected: 3
+            })
+        );
+
+        // The stToken must be oracle-driven
+        assert_eq!(
+            validate(
+                None,
+                Some(vec![
+                    AssetScalingFactor::OracleDriven {},
+                    AssetScalingFactor::NativeAnchor {},
+                    AssetScalingFactor::NativeAnchor {},
+                ])
+            ),
+            Err(ContractError::InvalidPoolAssetOrdering {})
+        );
+
+        // Only the stToken can be oracle-driven
+        assert_eq!(
+            validate(
+                None,
+                Some(vec![
+                    AssetScalingFactor::NativeAnchor {},
+                    AssetScalingFactor::OracleDriven {},
+                    AssetScalingFactor::OracleDriven {},
+                ])
+            ),
+            Err(ContractError::InvalidAssetScalingFactor { index: 2 })
+        );
+
+        // A paired stToken must reference the denom of the asset at the same index
+        assert_eq!(
+            validate(
+                None,
+                Some(vec![
+                    AssetScalingFactor::NativeAnchor {},
+                    AssetScalingFactor::OracleDriven {},
+                    AssetScalingFactor::PairedStToken {
+                        denom: "ibc/different".to_string(),
+                        oracle_contract_address: None,
+                    },
+                ])
+            ),
+            Err(ContractError::InvalidAssetScalingFactor { index: 2 })
+        );
+        let paired_asset_scaling_factors = vec![
+            AssetScalingFactor::NativeAnchor {},
+            AssetScalingFactor::OracleDriven {},
+            AssetScalingFactor::PairedStToken {
+                denom: "ibc/other".to_string(),
+                oracle_contract_address: None,
+            },
+        ];
+        assert_eq!(
+            validate(None, Some(paired_asset_scaling_factors.clone())),
+            Ok(paired_asset_scaling_factors)
+        );
+
+        // Fixed scaling factors cannot be zero
+        assert_eq!(
+            validate(
+                None,
+                Some(vec![
+                    AssetScalingFactor::NativeAnchor {},
+                    AssetScalingFactor::OracleDriven {},
+                    AssetScalingFactor::Fixed { scaling_factor: 0 },
+                ])
+            ),
+            Err(ContractError::InvalidAssetScalingFactor { index: 2 })
+        );
+    }
+
+    #[test]
+    fn test_validate_pool_configuration_derived_asset_ordering() {
+        let pool_id = 2;
+        let sttoken_denom = "ibc/sttoken";
+        let native_denom = "native";
+
+        // stToken first
+        let actual_pool = get_test_stableswap_pool(pool_id, vec![sttoken_denom, native_denom]);
+        assert_eq!(
+            validate_pool_configuration(
+                actual_pool,
+                pool_id,
+                sttoken_denom.to_string(),
+                None,
+                None,
+                TEST_CONTRACT_ADDRESS
+            ),
+            Ok(AssetOrdering::StTokenFirst.asset_scaling_factors())
+        );
+
+        // Native token first
+        let actual_pool = get_test_stableswap_pool(pool_id, vec![native_denom, sttoken_denom]);
+        assert_eq!(
+            validate_pool_configuration(
+                actual_pool,
+                pool_id,
+                sttoken_denom.to_string(),
+                None,
+                None,
+                TEST_CONTRACT_ADDRESS
+            ),
+            Ok(AssetOrdering::NativeTokenFirst.asset_scaling_factors())
+        );
+    }
+
+    #[test]
+    fn test_validate_pool_configuration_denom_not_in_pool() {
+        let pool_id = 2;
+        let actual_pool = get_test_stableswap_pool(pool_id, vec!["ibc/sttoken", "native"]);
+
+        // The error should be the same regardless of whether an ordering was specified
+        for asset_ordering in [None, Some(AssetOrdering::StTokenFirst)] {
+            assert_eq!(
+                validate_pool_configuration(
+                    actual_pool.clone(),
+                    pool_id,
+                    "ibc/other".to_string(),
+                    asset_ordering,
+                    None,
+                    TEST_CONTRACT_ADDRESS
+                ),
+                Err(ContractError::DenomNotInPool {
+                    pool_id,
+                    denom: "ibc/other".to_string()
+                })
+            );
+        }
+    }
 }
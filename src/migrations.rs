@@ -0,0 +1,142 @@
+use cosmwasm_std::{Addr, Order, StdResult, Storage};
+
+use crate::helpers::DEFAULT_SCALING_FACTOR_MULTIPLIER;
+use crate::state::{Config, Pool, RoundingMode, CONFIG, PAUSED, POOLS};
+use crate::ContractError;
+
+/// The default max oracle staleness assigned to the config when migrating from v1.0.0
+/// Redemption rates are updated every 6 hours, so this allows for a few missed updates
+pub const DEFAULT_MAX_ORACLE_STALENESS_SECONDS: u64 = 86_400;
+
+/// The default delay of manual scaling factor overrides assigned to the config when
+/// migrating from v1.0.0
+pub const DEFAULT_OVERRIDE_DELAY_SECONDS: u64 = 86_400;
+
+/// State layout from v1.0.0 of the contract
+pub mod v1_0_0 {
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::Addr;
+    use cw_storage_plus::{Item, Map};
+
+    use crate::state::AssetOrdering;
+
+    #[cw_serde]
+    pub struct Config {
+        pub admin_address: Addr,
+        pub oracle_contract_address: Addr,
+    }
+
+    #[cw_serde]
+    pub struct Pool {
+        pub pool_id: u64,
+        pub sttoken_denom: String,
+        pub asset_ordering: AssetOrdering,
+        pub last_updated: u64,
+    }
+
+    pub const CONFIG: Item<Config> = Item::new("config");
+    pub const POOLS: Map<u64, Pool> = Map::new("pools");
+}
+
+/// Parses a semantic version string (e.g. "1.0.0") into its major, minor, and patch components
+pub fn parse_version(version: &str) -> Result<(u64, u64, u64), ContractError> {
+    let invalid_version = || ContractError::InvalidContractVersion {
+        version: version.to_string(),
+    };
+
+    let components = version
+        .split('.')
+        .map(|component| component.parse::<u64>().map_err(|_| invalid_version()))
+        .collect::<Result<Vec<u64>, ContractError>>()?;
+
+    match components[..] {
+        [major, minor, patch] => Ok((major, minor, patch)),
+        _ => Err(invalid_version()),
+    }
+}
+
+/// Migrates the config and pools from the v1.0.0 layout to the current layout
+/// The config is assigned the provided guardian and max oracle staleness (with no minimum
+/// update interval or redemption rate offset, matching the v1.0.0 behavior), as well as the
+/// default override delay and no tolerance for redemption rate decreases (so that any
+/// decrease must be acknowledged by the admin), and each pool is migrated with no
+/// overrides, meaning it will use the config-level defaults
+pub fn migrate_from_v1_0_0(
+    storage: &mut dyn Storage,
+    guardian_address: Option<Addr>,
+    max_oracle_staleness_seconds: Option<u64>,
+) -> StdResult<()> {
+    let legacy_config = v1_0_0::CONFIG.load(storage)?;
+    let config = Config {
+        guardian_address: guardian_address.unwrap_or_else(|| legacy_config.admin_address.clone()),
+        admin_address: legacy_config.admin_address,
+        oracle_contract_address: legacy_config.oracle_contract_address,
+        max_oracle_staleness_seconds: max_oracle_staleness_seconds
+            .unwrap_or(DEFAULT_MAX_ORACLE_STALENESS_SECONDS),
+        min_update_interval_seconds: 0,
+        max_redemption_rate_offset_bps: 0,
+        override_delay_seconds: DEFAULT_OVERRIDE_DELAY_SECONDS,
+        oracle_quorum: 1,
+        max_redemption_rate_decrease_bps: 0,
+    };
+    CONFIG.save(storage, &config)?;
+    PAUSED.save(storage, &false)?;
+
+    let legacy_pools = v1_0_0::POOLS
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, pool)| pool))
+        .collect::<StdResult<Vec<v1_0_0::Pool>>>()?;
+
+    for legacy_pool in legacy_pools {
+        let pool = Pool {
+            pool_id: legacy_pool.pool_id,
+            sttoken_denom: legacy_pool.sttoken_denom,
+            asset_scaling_factors: legacy_pool.asset_ordering.asset_scaling_factors(),
+            scaling_factor_multiplier: DEFAULT_SCALING_FACTOR_MULTIPLIER,
+            rounding_mode: RoundingMode::Floor,
+            asset_decimals: None,
+            ramp_duration_seconds: None,
+            scaling_factor_ramp: None,
+            deadband_bps: None,
+            deadband_max_age_seconds: None,
+            redemption_rate_offset_bps: None,
+            twap_window_seconds: None,
+            last_updated: legacy_pool.last_updated,
+            max_oracle_staleness_seconds: None,
+            min_update_interval_seconds: None,
+            last_scaling_factors: vec![],
+            circuit_breaker: None,
+            last_redemption_rate: None,
+            last_oracle_update_time: None,
+            scaling_factor_override: None,
+            max_redemption_rate_decrease_bps: None,
+            redemption_rate_decrease_acknowledged: false,
+            paused: false,
+        };
+        POOLS.save(storage, pool.pool_id, &pool)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ContractError;
+
+    use super::parse_version;
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(parse_version("1.0.0"), Ok((1, 0, 0)));
+        assert_eq!(parse_version("12.34.56"), Ok((12, 34, 56)));
+
+        for invalid_version in ["", "1", "1.0", "1.0.0.0", "1.0.x", "v1.0.0"] {
+            assert_eq!(
+                parse_version(invalid_version),
+                Err(ContractError::InvalidContractVersion {
+                    version: invalid_version.to_string()
+                })
+            );
+        }
+    }
+}
@@ -1,40 +1,49 @@
-use crate::state::Pool;
+use crate::state::{
+    OracleAdapter, OracleSource, PendingScalingFactorOverride, Pool, RedemptionRateDecrease,
+    RedemptionRateSample,
+};
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Binary, Decimal};
+use std::fmt;
 
-use crate::state::AssetOrdering;
+use crate::state::{AssetOrdering, AssetScalingFactor, CircuitBreaker, RoundingMode};
 
-/// Instantiates the contract with an admin address and oracle contract address
+/// Instantiates the contract with an admin address, guardian address, and oracle contract address
 #[cw_serde]
 pub struct InstantiateMsg {
     pub admin_address: String,
+    pub guardian_address: String,
     pub oracle_contract_address: String,
+    pub max_oracle_staleness_seconds: u64,
+    pub min_update_interval_seconds: u64,
+    pub max_redemption_rate_offset_bps: u64,
+    pub override_delay_seconds: u64,
+    pub oracle_quorum: u64,
+    pub max_redemption_rate_decrease_bps: u64,
+}
+
+/// Migrates the contract state to the current version
+/// The fields are only used when migrating from v1.0.0, where they were not yet part of the config
+#[cw_serde]
+pub struct MigrateMsg {
+    /// The guardian address to add to the config (defaults to the admin address)
+    pub guardian_address: Option<String>,
+    /// The default max oracle staleness to add to the config (defaults to 1 day)
+    pub max_oracle_staleness_seconds: Option<u64>,
 }
 
 #[cw_serde]
 pub enum ExecuteMsg {
-    /// Updates the admin or oracle contract address from the config
-    UpdateConfig {
-        admin_address: String,
-        oracle_contract_address: String,
-    },
+    /// Updates any of the specified fields in the config
+    /// The admin can only be changed through the ProposeAdmin and AcceptAdmin flow
+    UpdateConfig(UpdateConfigMsg),
     /// Adds a new stToken stable swap pool
     /// Only the admin can add pool
-    AddPool {
-        /// Pool ID of the Osmosis pool (e.g. 833)
-        pool_id: u64,
-        /// The denom of the stToken as it lives on Osmosis (e.g. ibc/{hash(transfer/channel-0/stuosmo)})
-        /// This is the same denom that's in the oracle contract, and will line up with the denom in the
-        /// Osmosis pool
-        sttoken_denom: String,
-        /// The ordering of the stToken vs nativeToken assets in the Osmosis pool,
-        /// specifically with respect to the scaling factors
-        /// This will determine the ordering of elements in the scaling factor array
-        ///   e.g. If AssetOrdering::StTokenFirst, that means the assets in the pool are
-        ///        ordered as [stToken, nativeToken], and the native token must be scaled up
-        ///        So a redemption rate of 1.2 would imply a scaling factors array of [10000, 12000]
-        asset_ordering: AssetOrdering,
-    },
+    AddPool(AddPoolMsg),
+    /// Updates the configuration of a registered pool
+    /// Only the specified fields are modified
+    /// Only the admin can update a pool
+    UpdatePool(UpdatePoolMsg),
     /// Removes an stToken stable swap pool, preventing the pool from having it's scaling factors adjusted
     /// Only the admin can remove pools
     RemovePool { pool_id: u64 },
@@ -42,15 +51,232 @@ pub enum ExecuteMsg {
     /// from the ICA Oracle and submitting an `adjust-scaling-factor` transaction on Osmosis
     /// This message is permissionless
     UpdateScalingFactor { pool_id: u64 },
-    /// Allows the admin to bypass the query and adjust the scaling factor directly
-    /// This is meant as a safety mechanism after the contract is first deployed and
-    /// should eventually be removed
-    SudoAdjustScalingFactors {
+    /// Updates the scaling factors for multiple pools in a single transaction, querying the
+    /// oracle once per stToken
+    /// If pool IDs are not specified, all registered pools are updated, paginated with
+    /// `start_after` and `limit`
+    /// Pools that cannot be updated are skipped (with the reason included in the pool's
+    /// event) rather than failing the whole batch
+    /// This message is permissionless
+    UpdateAllScalingFactors {
+        pool_ids: Option<Vec<u64>>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Proposes a manual override of a pool's scaling factors, which can be executed once
+    /// the config's override delay has passed
+    /// Any existing proposal for the pool is replaced
+    /// Only the admin can propose an override
+    ProposeScalingFactorOverride {
+        pool_id: u64,
+        scaling_factors: Vec<u64>,
+        max_oracle_deviation_bps: Option<u64>,
+    },
+    /// Cancels a pending scaling factor override
+    /// Either the admin or guardian can cancel
+    CancelScalingFactorOverride { pool_id: u64 },
+    /// Applies a pending scaling factor override once the override delay has passed
+    /// This message is permissionless
+    ExecuteScalingFactorOverride { pool_id: u64 },
+    /// Allows the admin to accept the next redemption rate decrease of a pool that
+    /// exceeds the pool's tolerance (e.g. after a known slash)
+    /// The acknowledgement is consumed by the next update that applies the decrease
+    AcknowledgeRedemptionRateDecrease { pool_id: u64 },
+    /// Pauses scaling factor updates for a single pool, or for all pools if the pool ID is
+    /// not specified
+    /// Either the admin or guardian can pause
+    Pause { pool_id: Option<u64> },
+    /// Unpauses scaling factor updates for a single pool, or for all pools if the pool ID is
+    /// not specified
+    /// Only the admin can unpause
+    Unpause { pool_id: Option<u64> },
+    /// Proposes a new admin address, which must then be accepted by the new admin
+    /// An optional expiry (in seconds from now) can be provided, after which the
+    /// proposal can no longer be accepted
+    /// Only the admin can propose a new admin
+    ProposeAdmin {
+        admin_address: String,
+        expires_in_seconds: Option<u64>,
+    },
+    /// Accepts a pending admin proposal, transferring the admin role to the sender
+    /// Only the proposed admin can accept
+    AcceptAdmin {},
+    /// Cancels a pending admin proposal
+    /// Only the admin can cancel
+    CancelAdminProposal {},
+    /// Registers an additional oracle that's queried alongside the config's oracle, with
+    /// the adapter defining how the oracle is queried
+    /// Only the admin can add an oracle
+    AddOracleSource {
+        contract_address: String,
+        adapter: OracleAdapter,
+    },
+    /// Removes an additional oracle
+    /// Only the admin can remove an oracle
+    RemoveOracleSource { contract_address: String },
+}
+
+/// Messages that can only be submitted by chain governance
+#[cw_serde]
+pub enum SudoMsg {
+    /// Replaces the entire config, including the admin address
+    /// Any pending admin proposal is cancelled
+    ReplaceConfig(InstantiateMsg),
+    /// Removes an stToken stable swap pool
+    RemovePool { pool_id: u64 },
+    /// Pauses scaling factor updates for a single pool, or for all pools if the pool ID is
+    /// not specified
+    Pause { pool_id: Option<u64> },
+    /// Unpauses scaling factor updates for a single pool, or for all pools if the pool ID is
+    /// not specified
+    Unpause { pool_id: Option<u64> },
+    /// Forces the scaling factors of a pool, bypassing the oracle and the override delay
+    AdjustScalingFactors {
         pool_id: u64,
         scaling_factors: Vec<u64>,
+        max_oracle_deviation_bps: Option<u64>,
     },
 }
 
+/// Updates the contract config
+/// Fields that are not specified are left unchanged
+#[cw_serde]
+#[derive(Default)]
+pub struct UpdateConfigMsg {
+    pub guardian_address: Option<String>,
+    pub oracle_contract_address: Option<String>,
+    pub max_oracle_staleness_seconds: Option<u64>,
+    pub min_update_interval_seconds: Option<u64>,
+    pub max_redemption_rate_offset_bps: Option<u64>,
+    pub override_delay_seconds: Option<u64>,
+    pub oracle_quorum: Option<u64>,
+    pub max_redemption_rate_decrease_bps: Option<u64>,
+}
+
+/// Registers a new stToken stableswap pool
+#[cw_serde]
+pub struct AddPoolMsg {
+    /// Pool ID of the Osmosis pool (e.g. 833)
+    pub pool_id: u64,
+    /// The denom of the stToken as it lives on Osmosis (e.g. ibc/{hash(transfer/channel-0/stuosmo)})
+    /// This is the same denom that's in the oracle contract, and will line up with the denom in the
+    /// Osmosis pool
+    pub sttoken_denom: String,
+    /// The ordering of the stToken vs nativeToken assets in the Osmosis pool,
+    /// specifically with respect to the scaling factors
+    /// This will determine the ordering of elements in the scaling factor array
+    ///   e.g. If AssetOrdering::StTokenFirst, that means the assets in the pool are
+    ///        ordered as [stToken, nativeToken], and the native token must be scaled up
+    ///        So a redemption rate of 1.2 would imply a scaling factors array of [10000, 12000]
+    /// If not specified, the ordering is derived from the location of the stToken in the pool
+    /// Only applicable to pools with two assets
+    pub asset_ordering: Option<AssetOrdering>,
+    /// Describes how the scaling factor of each asset in the pool is determined, in the same
+    /// order as the pool's assets
+    /// Required for pools with more than two assets, and cannot be combined with the asset ordering
+    pub asset_scaling_factors: Option<Vec<AssetScalingFactor>>,
+    /// Optional multiplier used to convert redemption rates into integer scaling factors
+    /// (e.g. 1000000 for six decimals of precision). Defaults to 100000
+    pub scaling_factor_multiplier: Option<u64>,
+    /// Optional rounding mode used when converting redemption rates into scaling factors
+    /// Defaults to rounding down
+    pub rounding_mode: Option<RoundingMode>,
+    /// Optional decimal exponent of each asset (e.g. [6, 18]), in the same order as the
+    /// pool's assets. Only required if the assets have different decimals
+    pub asset_decimals: Option<Vec<u32>>,
+    /// Optional duration (in seconds) over which the scaling factors are ramped toward a new target
+    pub ramp_duration_seconds: Option<u64>,
+    /// Optional deadband (in basis points) under which scaling factor changes are skipped
+    pub deadband_bps: Option<u64>,
+    /// Optional max time (in seconds) since the last update, after which updates within
+    /// the deadband are applied
+    pub deadband_max_age_seconds: Option<u64>,
+    /// Optional signed offset (in basis points) applied to the oracle's redemption rate
+    /// (e.g. 50 for a 0.5% premium, or -50 for a 0.5% discount)
+    pub redemption_rate_offset_bps: Option<i64>,
+    /// Optional window (in seconds) over which the time-weighted average of the redemption
+    /// rate is applied, rather than the spot redemption rate
+    pub twap_window_seconds: Option<u64>,
+    /// Optional override of the config's max oracle staleness for this pool
+    pub max_oracle_staleness_seconds: Option<u64>,
+    /// Optional override of the config's minimum time between updates for this pool
+    pub min_update_interval_seconds: Option<u64>,
+    /// Optional limit on how far the scaling factors can move in a single update
+    pub circuit_breaker: Option<CircuitBreaker>,
+    /// Optional max decrease (in basis points) of the redemption rate in a single update
+    /// Larger decreases will be rejected unless acknowledged by the admin
+    /// If not specified, the config's default is used. A value of 10000 allows any decrease
+    pub max_redemption_rate_decrease_bps: Option<u64>,
+}
+
+/// Updates the configuration of a registered pool
+/// Fields that are not specified are left unchanged
+#[cw_serde]
+#[derive(Default)]
+pub struct UpdatePoolMsg {
+    pub pool_id: u64,
+    /// Override of the config's max oracle staleness for this pool
+    pub max_oracle_staleness_seconds: Option<u64>,
+    /// Override of the config's minimum time between updates for this pool
+    pub min_update_interval_seconds: Option<u64>,
+    /// Limit on how far the scaling factors can move in a single update
+    pub circuit_breaker: Option<CircuitBreaker>,
+    /// Max decrease (in basis points) of the redemption rate in a single update
+    pub max_redemption_rate_decrease_bps: Option<u64>,
+    /// Rounding mode used when converting redemption rates into scaling factors
+    pub rounding_mode: Option<RoundingMode>,
+    /// Duration (in seconds) over which the scaling factors are ramped toward a new target
+    /// A duration of zero disables ramping, cancelling any ramp in progress
+    pub ramp_duration_seconds: Option<u64>,
+    /// Deadband (in basis points) under which scaling factor changes are skipped
+    pub deadband_bps: Option<u64>,
+    /// Max time (in seconds) since the last update, after which updates within the
+    /// deadband are applied
+    pub deadband_max_age_seconds: Option<u64>,
+    /// Signed offset (in basis points) applied to the oracle's redemption rate
+    /// An offset of zero removes the premium or discount
+    pub redemption_rate_offset_bps: Option<i64>,
+    /// Window (in seconds) over which the time-weighted average of the redemption rate
+    /// is applied. A window of zero applies the spot redemption rate
+    pub twap_window_seconds: Option<u64>,
+    /// Per-pool settings to clear, reverting the pool to the config's default (or disabling
+    /// the feature if there is no default)
+    /// A setting cannot be both specified and cleared in the same message
+    pub clear_settings: Option<Vec<PoolSetting>>,
+}
+
+/// A per-pool setting that can be cleared with UpdatePool
+#[cw_serde]
+pub enum PoolSetting {
+    MaxOracleStalenessSeconds,
+    MinUpdateIntervalSeconds,
+    CircuitBreaker,
+    MaxRedemptionRateDecreaseBps,
+    RampDurationSeconds,
+    DeadbandBps,
+    DeadbandMaxAgeSeconds,
+    RedemptionRateOffsetBps,
+    TwapWindowSeconds,
+}
+
+impl fmt::Display for PoolSetting {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PoolSetting::MaxOracleStalenessSeconds => write!(f, "max_oracle_staleness_seconds"),
+            PoolSetting::MinUpdateIntervalSeconds => write!(f, "min_update_interval_seconds"),
+            PoolSetting::CircuitBreaker => write!(f, "circuit_breaker"),
+            PoolSetting::MaxRedemptionRateDecreaseBps => {
+                write!(f, "max_redemption_rate_decrease_bps")
+            }
+            PoolSetting::RampDurationSeconds => write!(f, "ramp_duration_seconds"),
+            PoolSetting::DeadbandBps => write!(f, "deadband_bps"),
+            PoolSetting::DeadbandMaxAgeSeconds => write!(f, "deadband_max_age_seconds"),
+            PoolSetting::RedemptionRateOffsetBps => write!(f, "redemption_rate_offset_bps"),
+            PoolSetting::TwapWindowSeconds => write!(f, "twap_window_seconds"),
+        }
+    }
+}
+
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
@@ -65,6 +291,52 @@ pub enum QueryMsg {
     /// Returns all pools controlled by the contract
     #[returns(Pools)]
     AllPools {},
+
+    /// Returns the pending admin proposal, if there is one
+    #[returns(Option<crate::state::PendingAdmin>)]
+    PendingAdmin {},
+
+    /// Returns whether scaling factor updates are paused for all pools
+    /// The pause status of an individual pool is included in the pool query
+    #[returns(bool)]
+    Paused {},
+
+    /// Returns the earliest time (in unix timestamp) at which the pool's scaling factors
+    /// can next be updated
+    #[returns(u64)]
+    NextUpdateTime { pool_id: u64 },
+
+    /// Returns the pool's current scaling factors, as well as the target and end time
+    /// of the ramp in progress (if the pool is ramping)
+    #[returns(ScalingFactorRampStatus)]
+    ScalingFactorRamp { pool_id: u64 },
+
+    /// Re-checks whether the contract is still the scaling factor controller of each
+    /// registered pool on Osmosis
+    #[returns(ScalingFactorControllers)]
+    ScalingFactorControllers {},
+
+    /// Returns each redemption rate decrease that was accepted for a pool
+    #[returns(RedemptionRateDecreases)]
+    RedemptionRateDecreases { pool_id: u64 },
+
+    /// Returns each scaling factor override that has been proposed but not yet
+    /// executed or cancelled
+    #[returns(PendingScalingFactorOverrides)]
+    PendingScalingFactorOverrides {},
+
+    /// Returns the recent redemption rates observed for an stToken, as well as their
+    /// time-weighted average over the window (or over every sample if the window is
+    /// not specified)
+    #[returns(RedemptionRateHistory)]
+    RedemptionRateHistory {
+        denom: String,
+        window_seconds: Option<u64>,
+    },
+
+    /// Returns each additional oracle that's queried alongside the config's oracle
+    #[returns(OracleSources)]
+    OracleSources {},
 }
 
 #[cw_serde]
@@ -72,6 +344,56 @@ pub struct Pools {
     pub pools: Vec<Pool>,
 }
 
+#[cw_serde]
+pub struct ScalingFactorRampStatus {
+    /// The scaling factors that were last applied to the pool
+    pub current_scaling_factors: Vec<u64>,
+    /// The scaling factors the pool is ramping toward
+    /// (the current scaling factors if there is no ramp in progress)
+    pub target_scaling_factors: Vec<u64>,
+    /// The time at which the ramp in progress reaches the target, if there is one
+    pub ramp_end_time: Option<u64>,
+}
+
+#[cw_serde]
+pub struct ScalingFactorControllers {
+    pub pools: Vec<ScalingFactorControllerStatus>,
+}
+
+#[cw_serde]
+pub struct ScalingFactorControllerStatus {
+    pub pool_id: u64,
+    /// The pool's current scaling factor controller on Osmosis
+    /// (None if the pool could not be queried)
+    pub scaling_factor_controller: Option<String>,
+    /// Indicates whether the contract is the pool's scaling factor controller
+    pub is_controller: bool,
+}
+
+#[cw_serde]
+pub struct RedemptionRateDecreases {
+    pub decreases: Vec<RedemptionRateDecrease>,
+}
+
+#[cw_serde]
+pub struct RedemptionRateHistory {
+    /// The recent redemption rates, ordered from oldest to newest
+    pub samples: Vec<RedemptionRateSample>,
+    /// The time-weighted average of the redemption rates up to the current block time
+    /// (None if no redemption rates have been observed)
+    pub time_weighted_average: Option<Decimal>,
+}
+
+#[cw_serde]
+pub struct PendingScalingFactorOverrides {
+    pub overrides: Vec<PendingScalingFactorOverride>,
+}
+
+#[cw_serde]
+pub struct OracleSources {
+    pub sources: Vec<OracleSource>,
+}
+
 /// RedemptionRate query as defined in the ICA Oracle contract
 #[cw_serde]
 #[derive(QueryResponses)]
@@ -89,3 +411,34 @@ pub struct RedemptionRateResponse {
     pub redemption_rate: Decimal,
     pub update_time: u64,
 }
+
+/// PriceFeed query as defined in the Pyth contract
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum PythQueryMsg {
+    #[returns(PythPriceFeedResponse)]
+    PriceFeed { id: String },
+}
+
+/// Response from Pyth price feed query
+#[cw_serde]
+pub struct PythPriceFeedResponse {
+    pub price_feed: PythPriceFeed,
+}
+
+#[cw_serde]
+pub struct PythPriceFeed {
+    pub id: String,
+    pub price: PythPrice,
+    pub ema_price: PythPrice,
+}
+
+/// A Pyth price, where the value is `price * 10^expo`
+/// The price and confidence interval are encoded as strings
+#[cw_serde]
+pub struct PythPrice {
+    pub price: String,
+    pub conf: String,
+    pub expo: i32,
+    pub publish_time: i64,
+}
@@ -1,5 +1,5 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Decimal};
 use cw_storage_plus::{Item, Map};
 use std::fmt;
 
@@ -11,6 +11,27 @@ pub struct Config {
     /// The oracle contract address represents the address of the ICA Oracle contract
     /// that contains the stToken redemption rates
     pub oracle_contract_address: Addr,
+    /// The default maximum age (in seconds) of an oracle redemption rate before it's
+    /// considered stale and rejected. Can be overridden for each pool
+    pub max_oracle_staleness_seconds: u64,
+    /// The guardian address is able to pause scaling factor updates (globally or for
+    /// an individual pool), but cannot unpause or modify the configuration
+    pub guardian_address: Addr,
+    /// The default minimum time (in seconds) between scaling factor updates of a pool
+    /// Can be overridden for each pool
+    pub min_update_interval_seconds: u64,
+    /// The maximum offset (in basis points, in either direction) that a pool can apply to
+    /// the oracle's redemption rate
+    pub max_redemption_rate_offset_bps: u64,
+    /// The delay (in seconds) between when a manual scaling factor override is proposed
+    /// and when it can be executed
+    pub override_delay_seconds: u64,
+    /// The minimum number of oracles that must return a valid redemption rate for an
+    /// stToken when additional oracle sources are registered
+    pub oracle_quorum: u64,
+    /// The default max decrease (in basis points) of the redemption rate in a single update
+    /// Can be overridden for each pool. A value of 10000 allows any decrease
+    pub max_redemption_rate_decrease_bps: u64,
 }
 
 /// Pool represents a stableswap pool that should have it's scaling factors adjusted
@@ -22,18 +43,77 @@ pub struct Pool {
     /// This is the same denom that's in the oracle contract, and will line up with the denom in the
     /// Osmosis pool
     pub sttoken_denom: String,
-    /// The ordering of the stToken vs nativeToken assets in the Osmosis pool,
-    /// specifically with respect to the scaling factors
+    /// Describes how the scaling factor of each asset in the Osmosis pool is determined,
+    /// in the same order as the pool's assets
     /// This will determine the ordering of elements in the scaling factor array
-    ///   e.g. If AssetOrdering::StTokenFirst, that means the assets in the pool are
-    ///        ordered as [stToken, nativeToken], and the native token must be scaled up
+    ///   e.g. If the pool's assets are ordered as [stToken, nativeToken], the descriptors
+    ///        would be [OracleDriven, NativeAnchor], and the native token must be scaled up
     ///        So a redemption rate of 1.2 would imply a scaling factors array of [10000, 12000]
-    pub asset_ordering: AssetOrdering,
+    pub asset_scaling_factors: Vec<AssetScalingFactor>,
+    /// The multiplier used to convert redemption rates into integer scaling factors, which
+    /// determines the precision of the scaling factors (e.g. 100000 gives five decimals)
+    pub scaling_factor_multiplier: u64,
+    /// Determines how the scaled redemption rate is rounded into an integer scaling factor
+    pub rounding_mode: RoundingMode,
+    /// Optional decimal exponent of each asset, in the same order as the pool's assets
+    /// If specified, the difference in decimals is folded into the scaling factors
+    /// If not specified, the assets are assumed to have the same decimals
+    pub asset_decimals: Option<Vec<u32>>,
+    /// Optional duration (in seconds) over which the scaling factors are moved toward a new
+    /// target, rather than jumping to the target in a single update
+    /// If not specified (or zero), the target is applied immediately
+    pub ramp_duration_seconds: Option<u64>,
+    /// The ramp that the scaling factors are currently moving along (in ramp mode)
+    pub scaling_factor_ramp: Option<ScalingFactorRamp>,
+    /// Optional deadband (in basis points), where updates that move every scaling factor by
+    /// less than the deadband are skipped to save gas and avoid churning the pool
+    pub deadband_bps: Option<u64>,
+    /// Optional max time (in seconds) since the last update, after which an update is
+    /// applied even if it's within the deadband
+    pub deadband_max_age_seconds: Option<u64>,
+    /// Optional signed offset (in basis points) applied to the oracle's redemption rate before
+    /// it's converted into scaling factors, where a positive offset is a premium and a
+    /// negative offset is a discount
+    /// Bounded by the config's max redemption rate offset
+    pub redemption_rate_offset_bps: Option<i64>,
+    /// Optional window (in seconds) over which the time-weighted average of the redemption
+    /// rate is applied, rather than the spot redemption rate from the oracle
+    /// If not specified (or zero), the spot redemption rate is applied
+    pub twap_window_seconds: Option<u64>,
     /// The last time (in unix timestamp) that the scaling factors were updated
     pub last_updated: u64,
+    /// Optional override of the config's max oracle staleness for this pool
+    /// If not specified, the config-level default is used
+    pub max_oracle_staleness_seconds: Option<u64>,
+    /// The scaling factors that were last applied to the pool, either from the oracle or
+    /// from a manual override (empty if the pool has not yet been updated)
+    pub last_scaling_factors: Vec<u64>,
+    /// Optional override of the config's minimum time between updates for this pool
+    /// If not specified, the config-level default is used
+    pub min_update_interval_seconds: Option<u64>,
+    /// Optional limit on how far the scaling factors can move in a single update
+    pub circuit_breaker: Option<CircuitBreaker>,
+    /// The redemption rate that was last applied to the pool from the oracle
+    pub last_redemption_rate: Option<Decimal>,
+    /// The oracle update time of the redemption rate that was last applied to the pool
+    pub last_oracle_update_time: Option<u64>,
+    /// The scaling factors that were manually set by the admin, if the pool has been
+    /// overridden since the last update from the oracle
+    pub scaling_factor_override: Option<ScalingFactorOverride>,
+    /// Optional max decrease (in basis points) of the redemption rate in a single update
+    /// Redemption rates should only decrease in the event of a slash, so larger decreases
+    /// are rejected unless acknowledged by the admin. If not specified, the config's default
+    /// is used
+    pub max_redemption_rate_decrease_bps: Option<u64>,
+    /// Indicates the admin has acknowledged that the next redemption rate decrease
+    /// may exceed the tolerance (e.g. after a known slash)
+    pub redemption_rate_decrease_acknowledged: bool,
+    /// Indicates scaling factor updates have been paused for this pool
+    pub paused: bool,
 }
 
 /// Defines the ordering of the two assets (stToken and native token) in a stable swap pool
+/// This is a shorthand for the asset scaling factors of a two-asset pool
 /// The scaling factors are an array where the index of each factor maps back to the two assets
 /// Redemption rate changes should modify the scaling factor that's tied to the native token
 /// meaning if the ordering is NativeTokenFirst, then the scaling factor array is [RedemptionRate, 1]
@@ -49,6 +129,22 @@ pub enum AssetOrdering {
     StTokenFirst,
 }
 
+impl AssetOrdering {
+    /// Returns the scaling factor descriptor of each asset in a two-asset pool with this ordering
+    pub fn asset_scaling_factors(&self) -> Vec<AssetScalingFactor> {
+        match self {
+            AssetOrdering::NativeTokenFirst => vec![
+                AssetScalingFactor::NativeAnchor {},
+                AssetScalingFactor::OracleDriven {},
+            ],
+            AssetOrdering::StTokenFirst => vec![
+                AssetScalingFactor::OracleDriven {},
+                AssetScalingFactor::NativeAnchor {},
+            ],
+        }
+    }
+}
+
 impl fmt::Display for AssetOrdering {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -58,8 +154,243 @@ impl fmt::Display for AssetOrdering {
     }
 }
 
+/// Defines how the scaling factor of an individual asset in a stable swap pool is determined
+/// Each pool has one descriptor per asset, which allows pools to have more than two assets
+///   e.g. A stATOM/ATOM/qATOM pool could be described as [OracleDriven, NativeAnchor, Fixed]
+#[cw_serde]
+pub enum AssetScalingFactor {
+    /// The pool's stToken, whose value (in terms of the native token) is given by the
+    /// redemption rate from the oracle
+    OracleDriven {},
+    /// The native token that the stToken redeems for
+    /// The native token's scaling factor is scaled up by the redemption rate
+    NativeAnchor {},
+    /// An asset whose scaling factor is constant and not adjusted by the contract
+    Fixed { scaling_factor: u64 },
+    /// A second stToken that redeems for the same native token (e.g. stkATOM in a
+    /// stATOM/stkATOM pool), whose redemption rate is also queried from an oracle
+    /// The scaling factor is derived from the ratio of the two redemption rates
+    PairedStToken {
+        /// The denom of the paired stToken in the oracle, which must line up with the
+        /// denom in the Osmosis pool
+        denom: String,
+        /// Optional oracle to query the paired stToken's redemption rate from
+        /// If not specified, the config's oracle is used. Any additional oracle sources
+        /// are still queried alongside it and subject to the quorum
+        oracle_contract_address: Option<String>,
+    },
+}
+
+impl fmt::Display for AssetScalingFactor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AssetScalingFactor::OracleDriven {} => write!(f, "oracle_driven"),
+            AssetScalingFactor::NativeAnchor {} => write!(f, "native_anchor"),
+            AssetScalingFactor::Fixed { scaling_factor } => write!(f, "fixed({})", scaling_factor),
+            AssetScalingFactor::PairedStToken { denom, .. } => {
+                write!(f, "paired_sttoken({})", denom)
+            }
+        }
+    }
+}
+
+/// A linear move of a pool's scaling factors from the initial scaling factors (at the start
+/// time) to the target scaling factors (at the end time)
+#[cw_serde]
+pub struct ScalingFactorRamp {
+    /// The scaling factors that were applied to the pool when the ramp started
+    pub initial_scaling_factors: Vec<u64>,
+    /// The scaling factors implied by the oracle, which the pool is moving toward
+    pub target_scaling_factors: Vec<u64>,
+    /// The time (in unix timestamp) that the ramp started
+    pub start_time: u64,
+    /// The time (in unix timestamp) at which the scaling factors reach the target
+    pub end_time: u64,
+}
+
+/// Scaling factors that were manually set by the admin, bypassing the oracle
+#[cw_serde]
+pub struct ScalingFactorOverride {
+    /// The scaling factors that were applied to the pool
+    pub scaling_factors: Vec<u64>,
+    /// The time (in unix timestamp) that the override was applied
+    pub time: u64,
+}
+
+/// Defines how a scaled redemption rate is rounded into an integer scaling factor
+#[cw_serde]
+pub enum RoundingMode {
+    /// Rounds down, which slightly under-prices the scaled asset
+    Floor,
+    /// Rounds up, which slightly over-prices the scaled asset
+    Ceil,
+    /// Rounds to the nearest integer, with ties rounded to the even integer
+    HalfEven,
+}
+
+impl fmt::Display for RoundingMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RoundingMode::Floor => write!(f, "floor"),
+            RoundingMode::Ceil => write!(f, "ceil"),
+            RoundingMode::HalfEven => write!(f, "half_even"),
+        }
+    }
+}
+
+/// Limits the relative change of each scaling factor from the last applied value
+/// This protects the pool from being repriced by a single bad oracle value
+#[cw_serde]
+pub struct CircuitBreaker {
+    /// The max relative change (in basis points) of any scaling factor in a single update
+    ///   e.g. 500 means each scaling factor can move at most 5% in either direction
+    pub max_change_bps: u64,
+    /// The behavior when an update exceeds the max change
+    pub action: CircuitBreakerAction,
+}
+
+/// Defines what happens when an update trips the circuit breaker
+/// In either case, a `scaling_factor_circuit_breaker` event is emitted
+#[cw_serde]
+pub enum CircuitBreakerAction {
+    /// Reject skips the update entirely, leaving the scaling factors unchanged
+    Reject,
+    /// Clamp moves each scaling factor as far as the max change allows towards the new value
+    Clamp,
+}
+
+impl fmt::Display for CircuitBreakerAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CircuitBreakerAction::Reject => write!(f, "reject"),
+            CircuitBreakerAction::Clamp => write!(f, "clamp"),
+        }
+    }
+}
+
+/// An additional oracle that's queried alongside the config's oracle, where the median
+/// of the redemption rates is applied
+#[cw_serde]
+pub struct OracleSource {
+    /// The address of the oracle contract
+    pub contract_address: Addr,
+    /// Determines how the oracle is queried for a redemption rate
+    pub adapter: OracleAdapter,
+}
+
+/// Defines the query interface of an oracle contract
+#[cw_serde]
+pub enum OracleAdapter {
+    /// Stride's ICA Oracle, which is queried with the stToken denom
+    IcaOracle {},
+    /// A Pyth price feed contract, where the redemption rate of each stToken is published
+    /// as the price of a dedicated feed
+    /// Each feed must publish the stToken's redemption rate (i.e. the stToken priced in its
+    /// native token), not the market price of the stToken
+    /// Prices with a confidence interval wider than the max (in basis points of the price)
+    /// are discarded
+    Pyth {
+        price_feeds: Vec<PythPriceFeedId>,
+        max_confidence_bps: u64,
+    },
+}
+
+impl fmt::Display for OracleAdapter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OracleAdapter::IcaOracle {} => write!(f, "ica_oracle"),
+            OracleAdapter::Pyth { .. } => write!(f, "pyth"),
+        }
+    }
+}
+
+/// Maps an stToken to the ID of the Pyth price feed that publishes its redemption rate
+#[cw_serde]
+pub struct PythPriceFeedId {
+    /// The stToken denom
+    pub denom: String,
+    /// The hex-encoded ID of the price feed
+    pub price_feed_id: String,
+}
+
+/// A proposed transfer of the admin role, which must be accepted by the new admin
+#[cw_serde]
+pub struct PendingAdmin {
+    /// The proposed admin address
+    pub address: Addr,
+    /// Optional time (in unix timestamp) after which the proposal can no longer be accepted
+    pub expires_at: Option<u64>,
+}
+
+/// A manual override of a pool's scaling factors that was proposed by the admin, which
+/// can be executed by anyone once the override delay has passed
+#[cw_serde]
+pub struct PendingScalingFactorOverride {
+    /// Pool ID of the Osmosis pool
+    pub pool_id: u64,
+    /// The scaling factors to apply to the pool
+    pub scaling_factors: Vec<u64>,
+    /// Optional max deviation (in basis points) from the scaling factors implied by the
+    /// oracle, checked when the override is executed
+    pub max_oracle_deviation_bps: Option<u64>,
+    /// The time (in unix timestamp) that the override was proposed
+    pub proposed_time: u64,
+    /// The earliest time (in unix timestamp) at which the override can be executed
+    pub executable_time: u64,
+}
+
+/// A redemption rate observed from the oracle
+#[cw_serde]
+pub struct RedemptionRateSample {
+    /// The time (in unix timestamp) that the redemption rate was updated in the oracle
+    pub update_time: u64,
+    /// The redemption rate
+    pub redemption_rate: Decimal,
+}
+
+/// Records a redemption rate decrease that was accepted by the contract
+#[cw_serde]
+pub struct RedemptionRateDecrease {
+    /// Pool ID of the Osmosis pool
+    pub pool_id: u64,
+    /// The redemption rate before the decrease
+    pub previous_redemption_rate: Decimal,
+    /// The redemption rate after the decrease
+    pub redemption_rate: Decimal,
+    /// The time (in unix timestamp) that the decrease was applied
+    pub time: u64,
+    /// Whether the decrease exceeded the pool's tolerance and was accepted
+    /// as a result of an admin acknowledgement
+    pub acknowledged: bool,
+}
+
 /// The CONFIG store stores contract configuration
 pub const CONFIG: Item<Config> = Item::new("config");
 
+/// The ORACLE_SOURCES store stores each additional oracle, key'd by the oracle contract address
+pub const ORACLE_SOURCES: Map<&Addr, OracleSource> = Map::new("oracle_sources");
+
+/// The PENDING_ADMIN store stores the proposed admin while a transfer is in progress
+pub const PENDING_ADMIN: Item<PendingAdmin> = Item::new("pending_admin");
+
+/// The PENDING_SCALING_FACTOR_OVERRIDES store stores each proposed scaling factor override
+/// until it's executed or cancelled, key'd by the pool ID
+pub const PENDING_SCALING_FACTOR_OVERRIDES: Map<u64, PendingScalingFactorOverride> =
+    Map::new("pending_scaling_factor_overrides");
+
+/// The PAUSED store indicates whether scaling factor updates are paused for all pools
+pub const PAUSED: Item<bool> = Item::new("paused");
+
 /// The POOLS store stores each Osmosis stableswap pool, key'd by the pool ID
 pub const POOLS: Map<u64, Pool> = Map::new("pools");
+
+/// The REDEMPTION_RATE_HISTORY store keeps a bounded buffer of the most recent redemption
+/// rates observed for each stToken (ordered from oldest to newest), key'd by the stToken denom
+pub const REDEMPTION_RATE_HISTORY: Map<&str, Vec<RedemptionRateSample>> =
+    Map::new("redemption_rate_history");
+
+/// The REDEMPTION_RATE_DECREASES store keeps an audit log of each accepted redemption rate
+/// decrease, key'd by the pool ID, time of the decrease, and a sequence number that
+/// distinguishes multiple decreases within the same block
+pub const REDEMPTION_RATE_DECREASES: Map<(u64, u64, u64), RedemptionRateDecrease> =
+    Map::new("redemption_rate_decreases");